@@ -5,6 +5,7 @@ pub struct Grid<T> {
 }
 
 impl<T> Grid<T> {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self {
             vec: Vec::new(),
@@ -151,6 +152,7 @@ impl<T> Grid<T> {
             })
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn into_iter(self) -> impl Iterator<Item = ((usize, usize), T)> {
         self.iter_by_row().zip(self.vec)
     }
@@ -6,6 +6,7 @@ pub struct Grid3D<T> {
 }
 
 impl<T> Grid3D<T> {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self {
             vec: Vec::new(),
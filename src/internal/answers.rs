@@ -0,0 +1,189 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use super::config::Config;
+use super::error::{AocError, Result};
+use super::Verdict;
+
+struct Record {
+    part: u32,
+    verdict: Verdict,
+    answer: String,
+}
+
+pub struct Answers {
+    path: PathBuf,
+    records: Vec<Record>,
+}
+
+impl Answers {
+    pub fn load(year: i32, day: &str, config: &Config, profile: Option<&str>) -> Result<Self> {
+        let path: PathBuf =
+            format!("{}/day{}.answers.txt", config.input_dir(profile, year), day).into();
+        let records = match std::fs::read_to_string(&path) {
+            Ok(content) => content
+                .lines()
+                .map(|line| {
+                    let mut parts = line.splitn(3, ' ');
+                    let part = parts.next().and_then(|p| p.parse().ok());
+                    let verdict = parts.next().and_then(parse_verdict);
+                    let answer = parts.next();
+                    match (part, verdict, answer) {
+                        (Some(part), Some(verdict), Some(answer)) => Ok(Record {
+                            part,
+                            verdict,
+                            answer: unescape(answer),
+                        }),
+                        _ => Err(AocError::InvalidFile(path.display().to_string())),
+                    }
+                })
+                .collect::<Result<_>>()?,
+            Err(_) => Vec::new(),
+        };
+        Ok(Self { path, records })
+    }
+
+    pub fn check(&self, part: u32, answer: &str) -> Option<Verdict> {
+        let records = || self.records.iter().filter(move |r| r.part == part);
+
+        if let Some(record) = records().find(|r| r.answer == answer) {
+            return Some(record.verdict);
+        }
+        if records().any(|r| r.verdict == Verdict::Correct) {
+            return Some(Verdict::Wrong);
+        }
+
+        let answer = answer.parse::<i128>().ok()?;
+        records().find_map(|r| match (r.verdict, r.answer.parse::<i128>()) {
+            (Verdict::TooHigh, Ok(bound)) if answer >= bound => Some(Verdict::TooHigh),
+            (Verdict::TooLow, Ok(bound)) if answer <= bound => Some(Verdict::TooLow),
+            _ => None,
+        })
+    }
+
+    pub fn record(&mut self, part: u32, answer: &str, verdict: Verdict) -> Result<()> {
+        if verdict_name(verdict).is_none() {
+            return Ok(());
+        }
+        self.records.push(Record {
+            part,
+            verdict,
+            answer: answer.to_string(),
+        });
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(AocError::io("Couldn't create parent directories"))?;
+        }
+        let mut file = std::fs::File::create(&self.path)
+            .map_err(AocError::io("Couldn't create answers file"))?;
+        for record in &self.records {
+            let verdict = verdict_name(record.verdict).unwrap();
+            writeln!(
+                file,
+                "{} {} {}",
+                record.part,
+                verdict,
+                escape(&record.answer)
+            )
+            .map_err(AocError::io("Couldn't write answers file"))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Status {
+    Correct,
+    Wrong,
+    Unknown,
+}
+
+impl Status {
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Correct => "correct",
+            Status::Wrong => "wrong",
+            Status::Unknown => "unknown",
+        }
+    }
+
+    pub fn mark(self) -> &'static str {
+        match self {
+            Status::Correct => " ✓",
+            Status::Wrong => " ✗",
+            Status::Unknown => "",
+        }
+    }
+}
+
+pub fn answer_status(
+    year: i32,
+    day: &str,
+    part: u32,
+    answer: &str,
+    config: &Config,
+    profile: Option<&str>,
+) -> Status {
+    let answers = match Answers::load(year, day, config, profile) {
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("Couldn't check the known answers: {}", error);
+            return Status::Unknown;
+        }
+    };
+    match answers.check(part, answer) {
+        Some(Verdict::Correct) => Status::Correct,
+        Some(_) => Status::Wrong,
+        None => Status::Unknown,
+    }
+}
+
+// One answer per line, so multi-line answers (like letters drawn in a grid) are escaped.
+fn escape(answer: &str) -> String {
+    answer
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape(answer: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => unescaped.push('\n'),
+            ('\\', Some('r')) => unescaped.push('\r'),
+            ('\\', Some('\\')) => unescaped.push('\\'),
+            _ => {
+                unescaped.push(c);
+                continue;
+            }
+        }
+        chars.next();
+    }
+    unescaped
+}
+
+fn verdict_name(verdict: Verdict) -> Option<&'static str> {
+    match verdict {
+        Verdict::Correct => Some("correct"),
+        Verdict::TooHigh => Some("too-high"),
+        Verdict::TooLow => Some("too-low"),
+        Verdict::Wrong => Some("wrong"),
+        Verdict::RateLimited(_) | Verdict::AlreadySolved => None,
+    }
+}
+
+fn parse_verdict(name: &str) -> Option<Verdict> {
+    match name {
+        "correct" => Some(Verdict::Correct),
+        "too-high" => Some(Verdict::TooHigh),
+        "too-low" => Some(Verdict::TooLow),
+        "wrong" => Some(Verdict::Wrong),
+        _ => None,
+    }
+}
@@ -0,0 +1,66 @@
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+
+use super::error::{AocError, Result};
+
+// Since 2025 the events only have 12 days. This is just the default,
+// `main!` can set a different length for each year.
+pub const fn days_in_year(year: i32) -> u32 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+// The first event was in 2015, and there can't be one after the current year.
+pub fn check_year(year: &str) -> Result<i32> {
+    match year.parse::<i32>() {
+        Ok(parsed_year)
+            if (2015..=Utc::now().with_timezone(&est()).year()).contains(&parsed_year) =>
+        {
+            Ok(parsed_year)
+        }
+        _ => Err(AocError::InvalidArgument(format!("Invalid year {}", year))),
+    }
+}
+
+pub fn is_last_day(day: &str, event_days: u32) -> bool {
+    day.parse() == Ok(event_days)
+}
+
+pub fn check_day(day: &str, event_days: u32) -> Result<u32> {
+    match day.parse::<u32>() {
+        Ok(parsed_day) if (1..=event_days).contains(&parsed_day) => Ok(parsed_day),
+        _ => Err(AocError::InvalidDay(day.to_string())),
+    }
+}
+
+// Puzzles unlock at midnight EST, which is UTC-5.
+fn est() -> FixedOffset {
+    FixedOffset::west_opt(5 * 3600).unwrap()
+}
+
+// `None` if the date can't be represented, for years far outside of the events.
+pub fn unlock_time(year: i32, day: u32) -> Option<DateTime<FixedOffset>> {
+    est().with_ymd_and_hms(year, 12, day, 0, 0, 0).single()
+}
+
+pub fn unlocked_days(year: i32, event_days: u32) -> u32 {
+    let now = Utc::now();
+    (1..=event_days)
+        .take_while(|&day| unlock_time(year, day).is_some_and(|unlock| unlock <= now))
+        .count() as u32
+}
+
+// The day whose puzzle unlocked today, if the event of `year` is running.
+pub fn current_day(year: i32, event_days: u32) -> Option<u32> {
+    let today = Utc::now().with_timezone(&est());
+    (today.year() == year && today.month() == 12 && today.day() <= event_days).then(|| today.day())
+}
+
+pub fn next_unlock(year: i32, event_days: u32) -> Option<(u32, DateTime<FixedOffset>)> {
+    let now = Utc::now();
+    (1..=event_days)
+        .filter_map(|day| Some((day, unlock_time(year, day)?)))
+        .find(|(_, unlock)| *unlock > now)
+}
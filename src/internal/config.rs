@@ -0,0 +1,127 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::error::{AocError, Result};
+
+const DEFAULT_INPUT_DIR: &str = "input";
+const DEFAULT_INPUT_FILE: &str = "{year}/day{day}.txt";
+const DEFAULT_TEMPLATE_DIR: &str = "templates";
+
+// Read once from `aoc.toml` or, if that's missing, from `[package.metadata.aoc]` in `Cargo.toml`.
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    #[serde(skip)]
+    src_dir: String,
+    input_dir: Option<String>,
+    input_file: Option<String>,
+    template_dir: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    package: Option<Package>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    metadata: Option<Metadata>,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    aoc: Option<Config>,
+}
+
+impl Config {
+    pub fn load(src_dir: &str) -> Result<Config> {
+        let invalid = |path: &str, error: toml::de::Error| {
+            AocError::InvalidFile(format!("{}: {}", path, error.to_string().trim_end()))
+        };
+
+        let path = format!("{}/aoc.toml", src_dir);
+        let config = match std::fs::read_to_string(&path) {
+            Ok(config) => toml::from_str(&config).map_err(|error| invalid(&path, error))?,
+            Err(_) => {
+                let path = format!("{}/Cargo.toml", src_dir);
+                match std::fs::read_to_string(&path) {
+                    Ok(manifest) => toml::from_str::<Manifest>(&manifest)
+                        .map_err(|error| invalid(&path, error))?
+                        .package
+                        .and_then(|package| package.metadata)
+                        .and_then(|metadata| metadata.aoc)
+                        .unwrap_or_default(),
+                    Err(_) => Config::default(),
+                }
+            }
+        };
+
+        let pattern = config.input_file();
+        if !pattern.contains("{day}") && !pattern.contains("{day:02}") {
+            return Err(AocError::InvalidArgument(format!(
+                "The input file pattern {} doesn't contain {{day}}",
+                pattern
+            )));
+        }
+        Ok(Config {
+            src_dir: src_dir.to_string(),
+            ..config
+        })
+    }
+
+    pub fn src_dir(&self) -> &str {
+        &self.src_dir
+    }
+
+    fn input_file(&self) -> &str {
+        self.input_file.as_deref().unwrap_or(DEFAULT_INPUT_FILE)
+    }
+
+    // The input directory relative to the crate, if it's a subdirectory of it.
+    pub fn local_input_dir(&self) -> Option<&str> {
+        let dir = self.input_dir.as_deref().unwrap_or(DEFAULT_INPUT_DIR);
+        let dir = dir.trim_start_matches("./").trim_end_matches('/');
+        let outside = dir.is_empty() || dir == "." || dir.starts_with("..");
+        (!outside && !Path::new(dir).is_absolute()).then_some(dir)
+    }
+
+    pub fn template_dir(&self) -> String {
+        self.resolve(self.template_dir.as_deref().unwrap_or(DEFAULT_TEMPLATE_DIR))
+    }
+
+    fn resolve(&self, dir: &str) -> String {
+        if Path::new(dir).is_absolute() {
+            dir.to_string()
+        } else {
+            format!("{}/{}", self.src_dir, dir)
+        }
+    }
+
+    // Each profile has its own inputs in the `$PROFILE` subdirectory of the input directory.
+    fn input_root(&self, profile: Option<&str>) -> String {
+        let root = self.resolve(self.input_dir.as_deref().unwrap_or(DEFAULT_INPUT_DIR));
+        match profile {
+            Some(profile) => format!("{}/{}", root, profile),
+            None => root,
+        }
+    }
+
+    // The directory with the descriptions, examples and answers of `year`.
+    pub fn input_dir(&self, profile: Option<&str>, year: i32) -> String {
+        format!("{}/{}", self.input_root(profile), year)
+    }
+
+    pub fn input_path(&self, profile: Option<&str>, year: i32, day: &str) -> String {
+        let padded_day = match day.parse::<u32>() {
+            Ok(day) => format!("{:02}", day),
+            Err(_) => day.to_string(),
+        };
+        let file = self
+            .input_file()
+            .replace("{year}", &year.to_string())
+            .replace("{day:02}", &padded_day)
+            .replace("{day}", day);
+        format!("{}/{}", self.input_root(profile), file)
+    }
+}
@@ -0,0 +1,83 @@
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum AocError {
+    MissingSession,
+    ExpiredSession,
+    NotUnlocked,
+    Http { url: String, status: u16 },
+    Network { url: String, message: String },
+    UnexpectedResponse { url: String },
+    InvalidDay(String),
+    InvalidArgument(String),
+    InvalidFile(String),
+    Io { context: String, source: io::Error },
+}
+
+pub type Result<T, E = AocError> = std::result::Result<T, E>;
+
+impl AocError {
+    pub fn io(context: impl Into<String>) -> impl FnOnce(io::Error) -> Self {
+        let context = context.into();
+        move |source| AocError::Io { context, source }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AocError::InvalidDay(_) | AocError::InvalidArgument(_) => 2,
+            AocError::MissingSession => 3,
+            AocError::ExpiredSession => 4,
+            AocError::NotUnlocked => 5,
+            AocError::Http { .. } => 6,
+            AocError::Network { .. } => 7,
+            AocError::UnexpectedResponse { .. } => 8,
+            AocError::InvalidFile(_) | AocError::Io { .. } => 9,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::MissingSession => write!(
+                f,
+                "No session token found, set it up with `cargo run -- session $SESSION` or $AOC_SESSION"
+            ),
+            AocError::ExpiredSession => write!(
+                f,
+                "The session token is invalid or expired, set a new one with `cargo run -- session $SESSION`"
+            ),
+            AocError::NotUnlocked => write!(f, "This puzzle hasn't been unlocked yet"),
+            AocError::Http { url, status } => {
+                write!(f, "Request to {} failed with status {}", url, status)
+            }
+            AocError::Network { url, message } => {
+                write!(f, "Request to {} failed: {}", url, message)
+            }
+            AocError::UnexpectedResponse { url } => {
+                write!(f, "Unexpected response from {}", url)
+            }
+            AocError::InvalidDay(day) => write!(f, "Invalid day {}", day),
+            AocError::InvalidArgument(message) => write!(f, "{}", message),
+            AocError::InvalidFile(path) => write!(f, "Invalid file {}", path),
+            AocError::Io { context, source } => write!(f, "{}: {}", context, source),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+pub fn exit_on_error(result: Result<()>) {
+    if let Err(error) = result {
+        eprintln!("Error: {}", error);
+        std::process::exit(error.exit_code());
+    }
+}
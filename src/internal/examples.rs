@@ -0,0 +1,146 @@
+use std::fmt::Display;
+
+use regex::Regex;
+
+use super::answers::Status;
+use super::config::Config;
+use super::error::{AocError, Result};
+use super::puzzle::decode_entities;
+
+pub struct Example {
+    pub part: u32,
+    pub input: &'static str,
+    pub answer: &'static dyn Display,
+}
+
+fn example_path(
+    year: i32,
+    day: &str,
+    example: u32,
+    config: &Config,
+    profile: Option<&str>,
+) -> String {
+    format!(
+        "{}/day{}.example{}.txt",
+        config.input_dir(profile, year),
+        day,
+        example
+    )
+}
+
+fn example_answers_path(
+    year: i32,
+    day: &str,
+    example: u32,
+    config: &Config,
+    profile: Option<&str>,
+) -> String {
+    format!(
+        "{}/day{}.example{}.answers.txt",
+        config.input_dir(profile, year),
+        day,
+        example
+    )
+}
+
+pub fn get_example_input(
+    year: i32,
+    day: &str,
+    example: u32,
+    config: &Config,
+    profile: Option<&str>,
+) -> Result<String> {
+    let path = example_path(year, day, example, config, profile);
+    if let Ok(input) = std::fs::read_to_string(&path) {
+        return Ok(input);
+    }
+
+    extract_examples(year, day, config, profile)?;
+    std::fs::read_to_string(&path).map_err(|_| {
+        AocError::InvalidArgument(format!("No example {} found for day {}", example, day))
+    })
+}
+
+// Every `<pre><code>` block of the description is a candidate example, and every
+// `<code><em>` following it is a candidate answer for it, up to the next example.
+// The second part usually reuses the last example of the first one, so its answers
+// are attached to whatever example came last.
+pub fn extract_examples(
+    year: i32,
+    day: &str,
+    config: &Config,
+    profile: Option<&str>,
+) -> Result<u32> {
+    let description = super::get_description(year, day, config, profile)?;
+    let token_re = Regex::new(
+        r"(?s)<article|<pre><code>(.*?)</code></pre>|<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>",
+    )
+    .unwrap();
+    let tag_re = Regex::new(r"<[^>]*>").unwrap();
+
+    let mut part = 0;
+    let mut examples = Vec::<(String, Vec<(u32, String)>)>::new();
+    for token in token_re.captures_iter(&description) {
+        if let Some(input) = token.get(1) {
+            let input = tag_re.replace_all(input.as_str(), "");
+            examples.push((decode_entities(&input), Vec::new()));
+        } else if let Some(answer) = token.get(2).or_else(|| token.get(3)) {
+            if let Some((_, answers)) = examples.last_mut() {
+                let answer = tag_re.replace_all(answer.as_str(), "");
+                answers.push((part, decode_entities(&answer)));
+            }
+        } else {
+            part += 1;
+        }
+    }
+
+    for (i, (input, answers)) in examples.iter().enumerate() {
+        let example = i as u32 + 1;
+        std::fs::write(example_path(year, day, example, config, profile), input)
+            .map_err(AocError::io("Couldn't write example file"))?;
+        let answers = answers
+            .iter()
+            .map(|(part, answer)| format!("{} {}\n", part, answer))
+            .collect::<String>();
+        std::fs::write(
+            example_answers_path(year, day, example, config, profile),
+            answers,
+        )
+        .map_err(AocError::io("Couldn't write example answers file"))?;
+    }
+
+    eprintln!(
+        "Extracted {} examples for day {} in {}",
+        examples.len(),
+        day,
+        config.input_dir(profile, year)
+    );
+    Ok(examples.len() as u32)
+}
+
+pub fn example_status(
+    year: i32,
+    day: &str,
+    example: u32,
+    part: u32,
+    answer: &str,
+    config: &Config,
+    profile: Option<&str>,
+) -> Status {
+    let path = example_answers_path(year, day, example, config, profile);
+    let candidates = std::fs::read_to_string(path).unwrap_or_default();
+    let mut candidates = candidates
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .filter(|(p, _)| p.parse() == Ok(part))
+        .map(|(_, candidate)| candidate)
+        .peekable();
+
+    if candidates.peek().is_none() {
+        Status::Unknown
+    } else if candidates.any(|candidate| candidate == answer) {
+        Status::Correct
+    } else {
+        Status::Wrong
+    }
+}
@@ -0,0 +1,124 @@
+use std::time::{Duration, SystemTime};
+
+use super::error::{AocError, Result};
+
+// All the requests go through this module, which identifies the helper with its
+// User-Agent and keeps a minimum interval between requests, even across processes.
+const MIN_INTERVAL: Duration = Duration::from_secs(1);
+const REPOSITORY: &str = "https://github.com/SkiFire13/adventofcode-helper-rs";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+// `AOC_BASE_URL` points the helper to another server, for example a local mock.
+pub fn base_url() -> String {
+    match std::env::var("AOC_BASE_URL") {
+        Ok(base_url) => base_url.trim_end_matches('/').to_string(),
+        Err(_) => DEFAULT_BASE_URL.to_string(),
+    }
+}
+
+pub fn url(path: &str) -> String {
+    format!("{}{}", base_url(), path)
+}
+
+fn user_agent() -> String {
+    if let Ok(user_agent) = std::env::var("AOC_USER_AGENT") {
+        return user_agent;
+    }
+    let mut user_agent = format!("aoc-helper/{} ({}", env!("CARGO_PKG_VERSION"), REPOSITORY);
+    if let Ok(contact) = std::env::var("AOC_CONTACT") {
+        user_agent.push_str(&format!(" by {}", contact));
+    }
+    user_agent.push(')');
+    user_agent
+}
+
+fn last_request_file() -> Option<std::path::PathBuf> {
+    let dirs = directories::ProjectDirs::from("com.github", "Giuschi", "Aoc-Session")?;
+    Some(dirs.cache_dir().join("last-request"))
+}
+
+// Only adventofcode.com is throttled, there's no need to be polite with a local mock.
+fn throttle() {
+    if base_url() != DEFAULT_BASE_URL {
+        return;
+    }
+    let path = match last_request_file() {
+        Some(path) => path,
+        None => return,
+    };
+    let elapsed = std::fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|last| SystemTime::now().duration_since(last).ok());
+    if let Some(elapsed) = elapsed.filter(|&elapsed| elapsed < MIN_INTERVAL) {
+        std::thread::sleep(MIN_INTERVAL - elapsed);
+    }
+
+    // Failing to record the request only means the next one won't wait.
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).ok();
+    }
+    std::fs::write(&path, "").ok();
+}
+
+pub fn create_agent(session: String) -> Result<ureq::Agent> {
+    let session_cookie = ureq::Cookie::new("session", session);
+
+    let base_url = base_url();
+    let adventofcode_url = url::Url::parse(&base_url)
+        .map_err(|_| AocError::InvalidArgument(format!("Invalid base URL {}", base_url)))?;
+
+    let mut cookie_store = Default::default();
+    // Hack to not depend on cookie_store
+    if false {
+        ureq::AgentBuilder::new().cookie_store(std::mem::take(&mut cookie_store));
+    }
+    cookie_store
+        .insert_raw(&session_cookie, &adventofcode_url)
+        .map_err(|_| AocError::ExpiredSession)?;
+
+    Ok(ureq::AgentBuilder::new()
+        .cookie_store(cookie_store)
+        .user_agent(&user_agent())
+        .build())
+}
+
+pub fn get(agent: &ureq::Agent, url: &str) -> Result<String> {
+    throttle();
+    read_response(url, agent.get(url).call())
+}
+
+pub fn post_form(agent: &ureq::Agent, url: &str, form: &[(&str, &str)]) -> Result<String> {
+    throttle();
+    read_response(url, agent.post(url).send_form(form))
+}
+
+fn read_response(
+    url: &str,
+    response: std::result::Result<ureq::Response, ureq::Error>,
+) -> Result<String> {
+    let network = |message: String| AocError::Network {
+        url: url.to_string(),
+        message,
+    };
+
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|error| network(error.to_string())),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            if body.contains("Please log in") {
+                Err(AocError::ExpiredSession)
+            } else if status == 404 && body.contains("before it unlocks") {
+                Err(AocError::NotUnlocked)
+            } else {
+                Err(AocError::Http {
+                    url: url.to_string(),
+                    status,
+                })
+            }
+        }
+        Err(ureq::Error::Transport(transport)) => Err(network(transport.to_string())),
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+
+use super::calendar::unlock_time;
+use super::config::Config;
+use super::error::{AocError, Result};
+use super::http;
+
+// AoC asks not to request private leaderboards more often than every 15 minutes.
+const CACHE_DURATION: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Deserialize)]
+struct Leaderboard {
+    members: HashMap<String, Member>,
+}
+
+#[derive(Deserialize)]
+struct Member {
+    id: u64,
+    name: Option<String>,
+    stars: u32,
+    local_score: u32,
+    completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Deserialize)]
+struct Star {
+    get_star_ts: i64,
+}
+
+impl Member {
+    fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    fn star_time(&self, day: u32, part: u32) -> Option<i64> {
+        let day = self.completion_day_level.get(&day.to_string())?;
+        day.get(&part.to_string()).map(|star| star.get_star_ts)
+    }
+}
+
+pub fn show_leaderboard(
+    year: i32,
+    event_days: u32,
+    id: &str,
+    day: Option<u32>,
+    config: &Config,
+    profile: Option<&str>,
+) -> Result<()> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()) {
+        return Err(AocError::InvalidArgument(format!(
+            "Invalid leaderboard id {}",
+            id
+        )));
+    }
+
+    let url = http::url(&format!("/{}/leaderboard/private/view/{}.json", year, id));
+    let json = get_leaderboard(&url, year, id, config, profile)?;
+    let leaderboard: Leaderboard = serde_json::from_str(&json)
+        .map_err(|_| AocError::UnexpectedResponse { url: url.clone() })?;
+
+    let mut members = leaderboard.members.values().collect::<Vec<_>>();
+    members.sort_by_key(|member| (std::cmp::Reverse(member.local_score), member.name()));
+
+    println!("Private leaderboard {} of {}", id, year);
+    println!();
+    print!("{}", scores_table(&members, event_days));
+    if let Some(day) = day {
+        println!();
+        print!("{}", day_table(&members, year, day)?);
+    }
+    Ok(())
+}
+
+fn get_leaderboard(
+    url: &str,
+    year: i32,
+    id: &str,
+    config: &Config,
+    profile: Option<&str>,
+) -> Result<String> {
+    let dir = config.input_dir(profile, year);
+    let path = format!("{}/leaderboard{}.json", dir, id);
+
+    let age = std::fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+    if let Some(age) = age.filter(|&age| age < CACHE_DURATION) {
+        if let Ok(json) = std::fs::read_to_string(&path) {
+            eprintln!(
+                "Using the leaderboard downloaded {} minutes ago",
+                age.as_secs() / 60
+            );
+            return Ok(json);
+        }
+    }
+
+    let agent = http::create_agent(super::get_session(config.src_dir(), profile)?)?;
+    let json = http::get(&agent, url)?;
+    std::fs::create_dir_all(&dir).map_err(AocError::io("Couldn't create parent directories"))?;
+    std::fs::write(&path, &json).map_err(AocError::io("Couldn't write leaderboard file"))?;
+    Ok(json)
+}
+
+// One column per day: `*` for both stars, `+` for the first one only.
+fn scores_table(members: &[&Member], event_days: u32) -> String {
+    let mut table = String::new();
+    let days = (1..=event_days)
+        .map(|day| char::from_digit(day % 10, 10).unwrap())
+        .collect::<String>();
+    writeln!(
+        table,
+        "{:>3} {:<24} {:>5} {:>5}  {}",
+        "#", "Name", "Score", "Stars", days
+    )
+    .unwrap();
+
+    for (rank, member) in members.iter().enumerate() {
+        let days = (1..=event_days)
+            .map(
+                |day| match (member.star_time(day, 1), member.star_time(day, 2)) {
+                    (Some(_), Some(_)) => '*',
+                    (Some(_), None) => '+',
+                    _ => '.',
+                },
+            )
+            .collect::<String>();
+        writeln!(
+            table,
+            "{:>3} {:<24} {:>5} {:>5}  {}",
+            rank + 1,
+            member.name(),
+            member.local_score,
+            member.stars,
+            days
+        )
+        .unwrap();
+    }
+    table
+}
+
+fn day_table(members: &[&Member], year: i32, day: u32) -> Result<String> {
+    let unlock = unlock_time(year, day)
+        .ok_or_else(|| AocError::InvalidArgument(format!("Invalid year {}", year)))?
+        .timestamp();
+
+    let mut members = members
+        .iter()
+        .filter_map(|member| Some((member, member.star_time(day, 1)?, member.star_time(day, 2))))
+        .collect::<Vec<_>>();
+    members.sort_by_key(|&(member, part1, part2)| (part2.is_none(), part2, part1, member.name()));
+
+    let mut table = String::new();
+    writeln!(table, "Day {}", day).unwrap();
+    writeln!(
+        table,
+        "{:<24} {:>10} {:>10} {:>10}",
+        "Name", "Part 1", "Part 2", "Delta"
+    )
+    .unwrap();
+    for (member, part1, part2) in members {
+        let elapsed =
+            |ts: Option<i64>| ts.map_or_else(|| "-".to_string(), |ts| duration(ts - unlock));
+        writeln!(
+            table,
+            "{:<24} {:>10} {:>10} {:>10}",
+            member.name(),
+            elapsed(Some(part1)),
+            elapsed(part2),
+            part2.map_or_else(|| "-".to_string(), |part2| duration(part2 - part1))
+        )
+        .unwrap();
+    }
+    Ok(table)
+}
+
+fn duration(seconds: i64) -> String {
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
@@ -0,0 +1,626 @@
+use clap::{App, Arg, SubCommand};
+use std::io::Write;
+
+mod answers;
+mod calendar;
+mod config;
+mod error;
+mod examples;
+mod http;
+mod leaderboard;
+mod progress;
+mod puzzle;
+mod readme;
+mod report;
+mod run;
+mod session;
+mod stats;
+mod storage;
+mod submit;
+mod template;
+mod wait;
+
+pub use answers::{answer_status, Status};
+pub use calendar::{
+    check_day, check_year, current_day, days_in_year, is_last_day, next_unlock, unlocked_days,
+};
+pub use config::Config;
+pub use error::{exit_on_error, AocError, Result};
+pub use examples::{extract_examples, get_example_input, Example};
+pub use leaderboard::show_leaderboard;
+pub use progress::show_progress;
+pub use puzzle::{get_description, html_to_markdown, read_puzzle};
+pub use report::{print_reports, DayReport, Format, PartReport};
+pub use run::{missing_part2, run_day, InputSource, MissingPart2, PartAnswer, RunOptions, Stats};
+pub use stats::show_stats;
+pub use submit::{submit_answer, Verdict};
+pub use wait::wait_and_download;
+
+use http::create_agent;
+use session::{check_session, get_session, set_session};
+use storage::{encrypt_inputs, input_exists, read_input, write_input};
+use template::render_template;
+
+pub struct Year<'a> {
+    pub year: i32,
+    pub module: Option<&'a str>,
+    pub days: &'a [&'a str],
+    pub event_days: u32,
+}
+
+pub const fn parse_year(module: &str) -> i32 {
+    let bytes = module.as_bytes();
+    assert!(
+        bytes.len() > 1 && bytes[0] == b'y',
+        "Year modules must be named like `y2022`"
+    );
+    let mut year = 0;
+    let mut i = 1;
+    while i < bytes.len() {
+        assert!(
+            bytes[i].is_ascii_digit(),
+            "Year modules must be named like `y2022`"
+        );
+        year = year * 10 + (bytes[i] - b'0') as i32;
+        i += 1;
+    }
+    year
+}
+
+pub fn run_clap(
+    years: &[Year],
+    src_dir: &str,
+    f: impl FnOnce(RunOptions) -> Result<()>,
+) -> Result<()> {
+    let about = years.iter().map(|y| y.year.to_string()).collect::<Vec<_>>();
+    let matches = App::new("My Super Program")
+        .author("Giacomo Stevanato <giaco.stevanato@gmail.com>")
+        .about(format!("My solutions to Advent of code {}", about.join(", ")).as_str())
+        .arg(
+            Arg::with_name("year")
+                .short("y")
+                .long("year")
+                .value_name("YEAR")
+                .help("Use the year $YEAR instead of the latest one")
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("profile")
+                .long("profile")
+                .value_name("PROFILE")
+                .help("Use the session and the inputs of the profile $PROFILE")
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("day")
+                .short("d")
+                .long("day")
+                .value_name("DAY")
+                .help("Run the solution for the day $DAY")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("submit")
+                .short("s")
+                .long("submit")
+                .value_name("PART")
+                .help("Submit the answer computed for the part $PART")
+                .possible_values(&["1", "2"])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("bench")
+                .short("b")
+                .long("bench")
+                .value_name("RUNS")
+                .help("Run each part $RUNS times and report timing statistics")
+                .takes_value(true)
+                .conflicts_with("submit"),
+        )
+        .arg(
+            Arg::with_name("example")
+                .short("e")
+                .long("example")
+                .value_name("EXAMPLE")
+                .help("Run the solution on the example $EXAMPLE extracted from the description")
+                .takes_value(true)
+                .conflicts_with("submit"),
+        )
+        .arg(
+            Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .value_name("PATH")
+                .help("Run the solution on the input file at $PATH")
+                .takes_value(true)
+                .conflicts_with_all(&["submit", "example"]),
+        )
+        .arg(
+            Arg::with_name("stdin")
+                .long("stdin")
+                .help("Run the solution on the input read from the standard input")
+                .conflicts_with_all(&["submit", "example", "input"]),
+        )
+        .arg(
+            Arg::with_name("format")
+                .short("f")
+                .long("format")
+                .value_name("FORMAT")
+                .help("Print the results as a human readable table, JSON or CSV")
+                .possible_values(&["table", "json", "csv"])
+                .default_value("table")
+                .takes_value(true),
+        )
+        .subcommand(
+            SubCommand::with_name("session")
+                .about("Sets the session token to use")
+                .arg(Arg::with_name("SESSION").required_unless("check"))
+                .arg(
+                    Arg::with_name("check")
+                        .long("check")
+                        .help("Check whether the stored session token is still valid")
+                        .conflicts_with("SESSION"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("input")
+                .about("Download an input file")
+                .arg(
+                    Arg::with_name("day")
+                        .short("d")
+                        .long("day")
+                        .value_name("DAY")
+                        .help("Download the input file for the day $DAY")
+                        .takes_value(true)
+                        .conflicts_with("wait"),
+                )
+                .arg(
+                    Arg::with_name("encrypt")
+                        .long("encrypt")
+                        .help("Encrypt the inputs already downloaded with the input key")
+                        .conflicts_with_all(&["day", "wait"]),
+                )
+                .arg(
+                    Arg::with_name("wait")
+                        .long("wait")
+                        .help("Wait for the next puzzle to unlock and download its input"),
+                )
+                .arg(
+                    Arg::with_name("setup")
+                        .long("setup")
+                        .help("Also setup the template file for the downloaded day")
+                        .requires("wait"),
+                )
+                .arg(
+                    Arg::with_name("template")
+                        .short("t")
+                        .long("template")
+                        .takes_value(true)
+                        .help("Use the template $TEMPLATE from the templates directory")
+                        .requires("setup"),
+                )
+                .arg(
+                    Arg::with_name("open")
+                        .long("open")
+                        .help("Open the template file with $VISUAL or $EDITOR")
+                        .requires("setup"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("setup")
+                .about("Setup the template file for the day $DAY")
+                .arg(Arg::with_name("DAY").required(true))
+                .arg(
+                    Arg::with_name("template")
+                        .short("t")
+                        .long("template")
+                        .takes_value(true)
+                        .help("Use the template $TEMPLATE from the templates directory"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("submit")
+                .about("Submit the answer $ANSWER for the part $PART")
+                .arg(
+                    Arg::with_name("PART")
+                        .required(true)
+                        .possible_values(&["1", "2"]),
+                )
+                .arg(Arg::with_name("ANSWER").required(true))
+                .arg(
+                    Arg::with_name("day")
+                        .short("d")
+                        .long("day")
+                        .value_name("DAY")
+                        .help("Submit the answer for the day $DAY")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("readme")
+                .about("Run all the days and update the timings table in README.md")
+                .arg(
+                    Arg::with_name("bench")
+                        .short("b")
+                        .long("bench")
+                        .value_name("RUNS")
+                        .help("Run each part $RUNS times and report the median timings")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("leaderboard")
+                .about("Show the private leaderboard $ID")
+                .arg(Arg::with_name("ID").required(true))
+                .arg(
+                    Arg::with_name("day")
+                        .short("d")
+                        .long("day")
+                        .value_name("DAY")
+                        .help("Show the completion times for the day $DAY")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("stats")
+                .about("Show your personal ranks and times")
+                .arg(
+                    Arg::with_name("format")
+                        .short("f")
+                        .long("format")
+                        .value_name("FORMAT")
+                        .help("Print the stats as a human readable table or JSON")
+                        .possible_values(&["table", "json"])
+                        .default_value("table")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("progress")
+                .about("Show the stars collected online next to the local days"),
+        )
+        .subcommand(
+            SubCommand::with_name("read")
+                .about("Download and print the puzzle description")
+                .arg(
+                    Arg::with_name("day")
+                        .short("d")
+                        .long("day")
+                        .value_name("DAY")
+                        .help("Print the puzzle description for the day $DAY")
+                        .takes_value(true),
+                ),
+        )
+        .get_matches();
+
+    let year = match matches.value_of("year") {
+        Some(year) => check_year(year)?,
+        None => years.last().expect("No year registered").year,
+    };
+    let profile = matches.value_of("profile");
+    if let Some(profile) = profile {
+        check_profile(profile)?;
+    }
+    let config = Config::load(src_dir)?;
+    let config = &config;
+    let registered = years.iter().find(|y| y.year == year);
+    let event_days = registered.map_or_else(|| days_in_year(year), |y| y.event_days);
+    let last_day = registered.and_then(|y| y.days.last()).map(|day| &day[3..]);
+    // Solutions can only be run and set up for the years registered in `main!`.
+    let check_registered = || match registered {
+        Some(_) => Ok(()),
+        None => Err(AocError::InvalidArgument(format!(
+            "The year {} isn't registered in main!",
+            year
+        ))),
+    };
+
+    match matches.subcommand() {
+        ("session", Some(session_args)) if session_args.is_present("check") => {
+            check_session(src_dir, profile)
+        }
+        ("session", Some(session_args)) => {
+            set_session(session_args.value_of("SESSION").unwrap_or(""), profile)
+        }
+        ("input", Some(input_args)) if input_args.is_present("encrypt") => {
+            let selected = matches.value_of("year").map(|_| year);
+            encrypt_inputs(years, selected, config, profile)
+        }
+        ("input", Some(input_args)) if input_args.is_present("wait") => {
+            if input_args.is_present("setup") {
+                check_registered()?;
+            }
+            let day = wait_and_download(year, event_days, config, profile)?.to_string();
+            if input_args.is_present("setup") {
+                let template = input_args.value_of("template");
+                let day_file = setup_day(years, year, &day, template, config, profile)?;
+                if input_args.is_present("open") {
+                    open_in_editor(&day_file)?;
+                }
+            }
+            Ok(())
+        }
+        ("input", Some(input_args)) => match input_args.value_of("day") {
+            Some("all") => download_all_inputs(year, event_days, config, profile),
+            Some(day) => {
+                check_day(day, event_days)?;
+                get_input(year, day, config, profile).map(drop)
+            }
+            None => match current_day(year, event_days) {
+                Some(day) => get_input(year, &day.to_string(), config, profile).map(drop),
+                None => download_all_inputs(year, event_days, config, profile),
+            },
+        },
+        ("setup", Some(setup_args)) => {
+            let day = setup_args.value_of("DAY").expect("Expected parameter");
+            check_registered()?;
+            check_day(day, event_days)?;
+            let template = setup_args.value_of("template");
+            setup_day(years, year, day, template, config, profile).map(drop)
+        }
+        ("submit", Some(submit_args)) => {
+            let day = submit_args.value_of("day").or(last_day).ok_or_else(|| {
+                AocError::InvalidArgument("No day to submit the answer for".to_string())
+            })?;
+            check_day(day, event_days)?;
+            let part = submit_args.value_of("PART").expect("Expected parameter");
+            let answer = submit_args.value_of("ANSWER").expect("Expected parameter");
+            submit_answer(year, day, part.parse().unwrap(), answer, config, profile).map(drop)
+        }
+        ("read", Some(read_args)) => {
+            let day = match read_args.value_of("day") {
+                Some(day) => day.to_string(),
+                None => match current_day(year, event_days) {
+                    Some(day) => day.to_string(),
+                    None => last_day
+                        .ok_or_else(|| {
+                            AocError::InvalidArgument(
+                                "No day to read the description for".to_string(),
+                            )
+                        })?
+                        .to_string(),
+                },
+            };
+            check_day(&day, event_days)?;
+            read_puzzle(year, &day, config, profile)?;
+            println!();
+            extract_examples(year, &day, config, profile).map(drop)
+        }
+        ("leaderboard", Some(leaderboard_args)) => {
+            let id = leaderboard_args.value_of("ID").expect("Expected parameter");
+            let day = match leaderboard_args.value_of("day") {
+                Some(day) => Some(check_day(day, event_days)?),
+                None => Some(unlocked_days(year, event_days)).filter(|&day| day > 0),
+            };
+            show_leaderboard(year, event_days, id, day, config, profile)
+        }
+        ("stats", Some(stats_args)) => {
+            let format = match stats_args.value_of("format") {
+                Some("json") => Format::Json,
+                _ => Format::Table,
+            };
+            show_stats(year, format, config, profile)
+        }
+        ("progress", Some(_)) => {
+            let selected = matches.value_of("year").map(|_| year);
+            show_progress(years, selected, config, profile)
+        }
+        ("readme", Some(readme_args)) => {
+            check_registered()?;
+            let bench = parse_runs(readme_args.value_of("bench"))?;
+            f(RunOptions {
+                config,
+                year,
+                event_days,
+                profile,
+                day: Some("all"),
+                submit: None,
+                bench,
+                input: InputSource::Puzzle,
+                format: Format::Table,
+                readme: true,
+            })
+        }
+        _ => {
+            check_registered()?;
+            let day = matches.value_of("day").or(last_day);
+            if let Some(day) = day.filter(|&day| day != "all") {
+                check_day(day, event_days)?;
+            }
+            let submit = matches.value_of("submit").map(|part| part.parse().unwrap());
+            if submit.is_some() && day == Some("all") {
+                return Err(AocError::InvalidArgument(
+                    "Can't submit answers when running all days".to_string(),
+                ));
+            }
+            let bench = parse_runs(matches.value_of("bench"))?;
+            let input = if let Some(example) = matches.value_of("example") {
+                InputSource::Example(example.parse().map_err(|_| {
+                    AocError::InvalidArgument(format!("Invalid example {}", example))
+                })?)
+            } else if let Some(path) = matches.value_of("input") {
+                InputSource::File(path)
+            } else if matches.is_present("stdin") {
+                InputSource::Stdin
+            } else {
+                InputSource::Puzzle
+            };
+            if !matches!(input, InputSource::Puzzle | InputSource::Example(_)) && day == Some("all")
+            {
+                return Err(AocError::InvalidArgument(
+                    "Can't use the same input when running all days".to_string(),
+                ));
+            }
+            let format = match matches.value_of("format") {
+                Some("json") => Format::Json,
+                Some("csv") => Format::Csv,
+                _ => Format::Table,
+            };
+            f(RunOptions {
+                config,
+                year,
+                event_days,
+                profile,
+                day,
+                submit,
+                bench,
+                input,
+                format,
+                readme: false,
+            })
+        }
+    }
+}
+
+fn setup_day(
+    years: &[Year],
+    year: i32,
+    day: &str,
+    template: Option<&str>,
+    config: &Config,
+    profile: Option<&str>,
+) -> Result<String> {
+    let src_dir = config.src_dir();
+    let module_dir = match years.iter().find(|y| y.year == year).map(|y| y.module) {
+        Some(Some(module)) => format!("{}/src/{}", src_dir, module),
+        _ if years.iter().any(|y| y.module.is_some()) => format!("{}/src/y{}", src_dir, year),
+        _ => format!("{}/src", src_dir),
+    };
+    std::fs::create_dir_all(&module_dir)
+        .map_err(AocError::io("Couldn't create parent directories"))?;
+
+    let template = render_template(template, year, day, config, profile)?;
+    let path = format!("{}/day{}.rs", module_dir, day);
+    let mut day_file = std::fs::OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .open(&path)
+        .map_err(AocError::io("Failed to create template file"))?;
+    write!(day_file, "{}", template).map_err(AocError::io("Failed to write to template file"))?;
+    Ok(path)
+}
+
+fn open_in_editor(path: &str) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .map_err(|_| {
+            AocError::InvalidArgument("Set $VISUAL or $EDITOR to open the day file".to_string())
+        })?;
+    std::process::Command::new(editor)
+        .arg(path)
+        .status()
+        .map_err(AocError::io("Couldn't start the editor"))?;
+    Ok(())
+}
+
+fn parse_runs(runs: Option<&str>) -> Result<Option<usize>> {
+    runs.map(|runs| match runs.parse() {
+        Ok(0) | Err(_) => Err(AocError::InvalidArgument(format!(
+            "Invalid number of runs {}",
+            runs
+        ))),
+        Ok(runs) => Ok(runs),
+    })
+    .transpose()
+}
+
+pub fn load_input(options: &RunOptions, year: i32, day: &str) -> Result<String> {
+    match options.input {
+        InputSource::Puzzle => get_input(year, day, options.config, options.profile),
+        InputSource::Example(example) => {
+            get_example_input(year, day, example, options.config, options.profile)
+        }
+        InputSource::File(path) => std::fs::read_to_string(path).map_err(AocError::io(format!(
+            "Couldn't read the input file {}",
+            path
+        ))),
+        InputSource::Stdin => {
+            let mut input = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut input)
+                .map_err(AocError::io("Couldn't read the input from stdin"))?;
+            Ok(input)
+        }
+    }
+}
+
+fn check_profile(profile: &str) -> Result<()> {
+    let valid = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    // Profiles named like years would clash with the inputs of the default profile.
+    if profile.is_empty() || !profile.chars().all(valid) || profile.parse::<i32>().is_ok() {
+        return Err(AocError::InvalidArgument(format!(
+            "Invalid profile {}",
+            profile
+        )));
+    }
+    Ok(())
+}
+
+pub fn get_input(year: i32, day: &str, config: &Config, profile: Option<&str>) -> Result<String> {
+    let path = config.input_path(profile, year, day);
+    if let Some(input) = read_input(&path, config.src_dir())? {
+        return Ok(input);
+    }
+
+    let agent = create_agent(get_session(config.src_dir(), profile)?)?;
+    download_input(&agent, year, day, config, profile)
+}
+
+fn download_input(
+    agent: &ureq::Agent,
+    year: i32,
+    day: &str,
+    config: &Config,
+    profile: Option<&str>,
+) -> Result<String> {
+    let destination = config.input_path(profile, year, day);
+    // Inputs never change, so they are never downloaded again.
+    if let Some(input) = read_input(&destination, config.src_dir())? {
+        return Ok(input);
+    }
+
+    eprint!("     - Downloading input for day {:<2}... ", day);
+
+    let url = http::url(&format!("/{}/day/{}/input", year, day));
+    let body = http::get(agent, &url)?;
+
+    if body == "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n" {
+        return Err(AocError::ExpiredSession);
+    }
+
+    write_input(&destination, config, &body)?;
+
+    eprintln!("Input downloaded");
+
+    Ok(body)
+}
+
+fn download_all_inputs(
+    year: i32,
+    event_days: u32,
+    config: &Config,
+    profile: Option<&str>,
+) -> Result<()> {
+    let agent = create_agent(get_session(config.src_dir(), profile)?)?;
+
+    let max_day = unlocked_days(year, event_days);
+    if max_day == 0 {
+        eprintln!("AdventOfCode {} hasn't started yet!", year);
+        return Ok(());
+    }
+
+    for day in 1..=max_day {
+        let day = format!("{}", day);
+        eprint!("Checking input for day {:<2} year {}.", day, year);
+
+        let path = config.input_path(profile, year, &day);
+        if input_exists(&path) {
+            eprintln!("     - Input already downloaded.");
+        } else {
+            download_input(&agent, year, &day, config, profile)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use regex::Regex;
+
+use super::calendar::days_in_year;
+use super::config::Config;
+use super::error::{AocError, Result};
+use super::storage::input_exists;
+use super::{http, Year};
+
+pub fn show_progress(
+    years: &[Year],
+    selected: Option<i32>,
+    config: &Config,
+    profile: Option<&str>,
+) -> Result<()> {
+    let agent = http::create_agent(super::get_session(config.src_dir(), profile)?)?;
+
+    let events = get_events(&agent)?;
+    let events = events
+        .into_iter()
+        .filter(|(_, stars)| *stars > 0)
+        .collect::<Vec<_>>();
+    if events.is_empty() {
+        println!("No stars collected yet");
+    } else {
+        let stars = events
+            .iter()
+            .map(|(year, stars)| format!("{} {}*", year, stars))
+            .collect::<Vec<_>>();
+        println!("Stars: {}", stars.join("  "));
+    }
+
+    // The years with stars but no solutions are shown too, as they are the ones left to port.
+    let shown = match selected {
+        Some(year) => vec![year],
+        None => {
+            let mut shown = events.iter().map(|&(year, _)| year).collect::<Vec<_>>();
+            shown.extend(years.iter().map(|y| y.year));
+            shown.sort_unstable();
+            shown.dedup();
+            shown
+        }
+    };
+    for year in shown {
+        let registered = years.iter().find(|y| y.year == year);
+        let event_days = registered.map_or_else(|| days_in_year(year), |y| y.event_days);
+        let stars = get_stars(&agent, year)?;
+
+        let row = |f: &dyn Fn(u32) -> char| (1..=event_days).map(f).collect::<String>();
+        println!();
+        println!(
+            "{:<9}{}",
+            year,
+            row(&|day| char::from_digit(day % 10, 10).unwrap())
+        );
+        println!(
+            "{:<9}{}",
+            "Stars",
+            row(&|day| match stars.get(day as usize - 1) {
+                Some(2) => '*',
+                Some(1) => '+',
+                _ => '.',
+            })
+        );
+        println!(
+            "{:<9}{}",
+            "Solved",
+            row(&|day| {
+                let module = format!("day{}", day);
+                match registered {
+                    Some(y) if y.days.contains(&module.as_str()) => 'x',
+                    _ => '.',
+                }
+            })
+        );
+        println!(
+            "{:<9}{}",
+            "Input",
+            row(&|day| {
+                if input_exists(&config.input_path(profile, year, &day.to_string())) {
+                    'x'
+                } else {
+                    '.'
+                }
+            })
+        );
+    }
+    Ok(())
+}
+
+fn get_events(agent: &ureq::Agent) -> Result<Vec<(i32, u32)>> {
+    let url = http::url("/events");
+    let body = http::get(agent, &url)?;
+
+    let event_re =
+        Regex::new(r#"<a href="/(\d+)">\[\d+\]</a>(?:\s*<span class="star-count">\s*(\d+)\*)?"#)
+            .unwrap();
+    let mut events = event_re
+        .captures_iter(&body)
+        .map(|caps| {
+            let stars = caps
+                .get(2)
+                .map_or(0, |stars| stars.as_str().parse().unwrap());
+            (caps[1].parse().unwrap(), stars)
+        })
+        .collect::<Vec<_>>();
+    if events.is_empty() {
+        return Err(AocError::UnexpectedResponse { url });
+    }
+    events.sort_unstable();
+    Ok(events)
+}
+
+// The stars of each day, as shown by the classes of the calendar on the year page.
+fn get_stars(agent: &ureq::Agent, year: i32) -> Result<Vec<u32>> {
+    let url = http::url(&format!("/{}", year));
+    let body = http::get(agent, &url)?;
+
+    let day_re = Regex::new(r#"<a [^>]*class="calendar-day(\d+)([^"]*)""#).unwrap();
+    let mut stars = Vec::new();
+    for caps in day_re.captures_iter(&body) {
+        let day: usize = caps[1].parse().unwrap();
+        if stars.len() < day {
+            stars.resize(day, 0);
+        }
+        stars[day - 1] = if caps[2].contains("calendar-verycomplete") {
+            2
+        } else if caps[2].contains("calendar-complete") {
+            1
+        } else {
+            0
+        };
+    }
+    Ok(stars)
+}
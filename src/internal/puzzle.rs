@@ -0,0 +1,135 @@
+use regex::Regex;
+
+use super::config::Config;
+use super::error::{AocError, Result};
+use super::http;
+
+pub fn read_puzzle(year: i32, day: &str, config: &Config, profile: Option<&str>) -> Result<()> {
+    let description = get_description(year, day, config, profile)?;
+    println!("{}", html_to_markdown(&description));
+    Ok(())
+}
+
+// The cached description is used directly only when it already contains both parts,
+// otherwise it's downloaded again in case the second part has been unlocked since.
+pub fn get_description(
+    year: i32,
+    day: &str,
+    config: &Config,
+    profile: Option<&str>,
+) -> Result<String> {
+    let dir = config.input_dir(profile, year);
+    let path = format!("{}/day{}.html", dir, day);
+    let cached = std::fs::read_to_string(&path).ok();
+    if let Some(cached) = &cached {
+        if cached.matches("<article").count() >= 2 {
+            return Ok(cached.clone());
+        }
+    }
+
+    let description = super::get_session(config.src_dir(), profile)
+        .and_then(http::create_agent)
+        .and_then(|agent| download_description(&agent, year, day));
+    match description {
+        Ok(description) => {
+            std::fs::create_dir_all(&dir)
+                .map_err(AocError::io("Couldn't create parent directories"))?;
+            std::fs::write(&path, &description)
+                .map_err(AocError::io("Couldn't write description file"))?;
+            Ok(description)
+        }
+        Err(error) => match cached {
+            Some(cached) => {
+                eprintln!(
+                    "Couldn't download the description, using the cached one: {}",
+                    error
+                );
+                Ok(cached)
+            }
+            None => Err(error),
+        },
+    }
+}
+
+fn download_description(agent: &ureq::Agent, year: i32, day: &str) -> Result<String> {
+    let url = http::url(&format!("/{}/day/{}", year, day));
+    let body = http::get(agent, &url)?;
+
+    let article_re = Regex::new(r#"(?s)<article class="day-desc">.*?</article>"#).unwrap();
+    let articles = article_re
+        .find_iter(&body)
+        .map(|article| article.as_str())
+        .collect::<Vec<_>>();
+    if articles.is_empty() {
+        return Err(AocError::UnexpectedResponse { url });
+    }
+    Ok(articles.join("\n"))
+}
+
+pub fn html_to_markdown(html: &str) -> String {
+    let token_re = Regex::new(r#"(?s)<(/?)(\w+)([^>]*)>|([^<]+)"#).unwrap();
+    let href_re = Regex::new(r#"href="([^"]*)""#).unwrap();
+
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut links = Vec::new();
+
+    for token in token_re.captures_iter(html) {
+        if let Some(text) = token.get(4) {
+            let text = text.as_str();
+            if in_pre || !text.trim().is_empty() || !text.contains('\n') {
+                markdown.push_str(&decode_entities(text));
+            }
+            continue;
+        }
+
+        let closing = &token[1] == "/";
+        match (&token[2], closing) {
+            ("h2", false) => markdown.push_str("## "),
+            ("h2", true) => markdown.push_str("\n\n"),
+            ("p", true) => markdown.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                markdown.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => markdown.push('`'),
+            ("em", _) if !in_pre => markdown.push_str("**"),
+            ("li", false) => markdown.push_str("- "),
+            ("li", true) => markdown.push('\n'),
+            ("ul", true) => markdown.push('\n'),
+            ("a", false) => {
+                let href = href_re.captures(&token[3]).map(|caps| caps[1].to_string());
+                links.push(href);
+                markdown.push('[');
+            }
+            ("a", true) => match links.pop().flatten() {
+                Some(href) if href.starts_with('/') => {
+                    markdown.push_str(&format!("]({})", http::url(&href)))
+                }
+                Some(href) => markdown.push_str(&format!("]({})", href)),
+                None => markdown.push(']'),
+            },
+            _ => {}
+        }
+    }
+
+    // Emphasis inside inline code wouldn't be rendered, so move it outside.
+    let code_em_re = Regex::new(r"`\*\*([^`*]*)\*\*`").unwrap();
+    let markdown = code_em_re.replace_all(&markdown, "**`$1`**");
+    markdown.trim_end().to_string()
+}
+
+pub fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
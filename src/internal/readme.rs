@@ -0,0 +1,83 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use super::error::{AocError, Result};
+use super::report::DayReport;
+
+// Each year has its own table between its own markers, so crates with multiple years keep
+// the timings of all of them.
+pub fn update_readme(src_dir: &str, year: i32, reports: &[DayReport]) -> Result<()> {
+    let path = format!("{}/README.md", src_dir);
+    let readme = match std::fs::read_to_string(&path) {
+        Ok(readme) => readme,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(error) => return Err(AocError::io("Couldn't read README.md")(error)),
+    };
+    let start_marker = format!("<!-- aoc-helper timings {} start -->", year);
+    let end_marker = format!("<!-- aoc-helper timings {} end -->", year);
+    let table = timings_table(reports);
+
+    let readme = match (readme.find(&start_marker), readme.find(&end_marker)) {
+        (Some(start), Some(end)) if start < end => format!(
+            "{}{}\n{}{}",
+            &readme[..start],
+            start_marker,
+            table,
+            &readme[end..]
+        ),
+        _ => {
+            let separator = if readme.is_empty() || readme.ends_with("\n\n") {
+                ""
+            } else if readme.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            };
+            format!(
+                "{}{}## {}\n\n{}\n{}{}\n",
+                readme, separator, year, start_marker, table, end_marker
+            )
+        }
+    };
+
+    std::fs::write(&path, readme).map_err(AocError::io("Couldn't write README.md"))?;
+    println!("Updated the timings in {}", path);
+    Ok(())
+}
+
+fn timings_table(reports: &[DayReport]) -> String {
+    let mut table = String::new();
+    table.push_str("| Day | Parsing | Part 1 | Part 2 | Total |\n");
+    table.push_str("|----:|--------:|-------:|-------:|------:|\n");
+
+    let mut totals = [Duration::default(); 4];
+    for report in reports {
+        let part_time = |part: u32| report.parts.iter().find(|p| p.part == part).map(|p| p.time);
+        let times = [
+            Some(report.parse_time),
+            part_time(1),
+            part_time(2),
+            Some(report.total()),
+        ];
+
+        write!(table, "| {} |", report.day).unwrap();
+        for (time, total) in times.iter().zip(&mut totals) {
+            match time {
+                Some(time) => {
+                    *total += *time;
+                    write!(table, " {:.3?} |", time).unwrap();
+                }
+                None => table.push_str(" - |"),
+            }
+        }
+        table.push('\n');
+    }
+
+    table.push_str("| **Total** |");
+    for total in totals {
+        write!(table, " **{:.3?}** |", total).unwrap();
+    }
+    table.push('\n');
+
+    table
+}
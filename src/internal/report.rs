@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use super::answers::Status;
+use super::error::Result;
+use super::readme::update_readme;
+use super::run::RunOptions;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+pub struct PartReport {
+    pub part: u32,
+    pub answer: String,
+    pub time: Duration,
+    pub status: Status,
+}
+
+pub struct DayReport {
+    pub day: String,
+    pub parse_time: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+impl DayReport {
+    pub fn total(&self) -> Duration {
+        self.parse_time + self.parts.iter().map(|part| part.time).sum::<Duration>()
+    }
+}
+
+// One record for each part in the JSON output, with the times in nanoseconds.
+#[derive(Serialize)]
+struct Record<'a> {
+    day: u32,
+    part: u32,
+    answer: &'a str,
+    parse_time_ns: u128,
+    solve_time_ns: u128,
+    status: &'static str,
+}
+
+pub fn print_reports(options: &RunOptions, src_dir: &str, reports: &[DayReport]) -> Result<()> {
+    match options.format {
+        Format::Table => {
+            if reports.is_empty() {
+                println!("No matching day was found")
+            } else {
+                let total = reports.iter().map(DayReport::total).sum::<Duration>();
+                println!("Took in total: {:.3?}", total);
+            }
+            println!();
+        }
+        Format::Json => {
+            let records = records(reports)
+                .map(|(day, part)| Record {
+                    day: day.day.parse().unwrap_or_default(),
+                    part: part.part,
+                    answer: &part.answer,
+                    parse_time_ns: day.parse_time.as_nanos(),
+                    solve_time_ns: part.time.as_nanos(),
+                    status: part.status.name(),
+                })
+                .collect::<Vec<_>>();
+            println!("{}", serde_json::to_string(&records).unwrap());
+        }
+        Format::Csv => {
+            println!("day,part,answer,parse_time_ns,solve_time_ns,status");
+            for (day, part) in records(reports) {
+                println!(
+                    "{},{},{},{},{},{}",
+                    day.day,
+                    part.part,
+                    csv_field(&part.answer),
+                    day.parse_time.as_nanos(),
+                    part.time.as_nanos(),
+                    part.status.name()
+                );
+            }
+        }
+    }
+
+    if options.readme && !reports.is_empty() {
+        update_readme(src_dir, options.year, reports)?;
+    }
+    Ok(())
+}
+
+fn records(reports: &[DayReport]) -> impl Iterator<Item = (&DayReport, &PartReport)> {
+    reports
+        .iter()
+        .flat_map(|day| day.parts.iter().map(move |part| (day, part)))
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
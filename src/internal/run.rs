@@ -0,0 +1,322 @@
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+use super::answers::Status;
+use super::calendar::is_last_day;
+use super::config::Config;
+use super::error::Result;
+use super::examples::example_status;
+use super::report::{DayReport, Format, PartReport};
+use super::{answer_status, submit_answer};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InputSource<'a> {
+    Puzzle,
+    Example(u32),
+    File(&'a str),
+    Stdin,
+}
+
+pub struct RunOptions<'a> {
+    pub config: &'a Config,
+    pub year: i32,
+    pub event_days: u32,
+    pub profile: Option<&'a str>,
+    pub day: Option<&'a str>,
+    pub submit: Option<u32>,
+    pub bench: Option<usize>,
+    pub input: InputSource<'a>,
+    pub format: Format,
+    pub readme: bool,
+}
+
+// Used by `main!` in place of the `part2` of days that don't define a public one.
+pub struct MissingPart2;
+
+pub fn missing_part2<I>(_: &I) -> MissingPart2 {
+    MissingPart2
+}
+
+pub trait PartAnswer {
+    fn answer(&self) -> Option<String>;
+}
+
+impl<T: Display> PartAnswer for T {
+    fn answer(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl PartAnswer for MissingPart2 {
+    fn answer(&self) -> Option<String> {
+        None
+    }
+}
+
+fn report_missing_part2(options: &RunOptions, day: &str) {
+    if options.format == Format::Table {
+        println!("     - Part 2: not implemented (no `pub fn part2` found)");
+        println!();
+    } else {
+        eprintln!("Day {} part 2 is not implemented", day);
+    }
+}
+
+pub fn run_day<'i, I, P1: Display, P2: PartAnswer>(
+    options: &RunOptions,
+    year: i32,
+    day: &str,
+    input: &'i str,
+    input_generator: impl Fn(&'i str) -> I,
+    part1: impl Fn(&mut I) -> P1,
+    part2: Option<impl Fn(&mut I) -> P2>,
+) -> Result<DayReport> {
+    let table = options.format == Format::Table;
+    if table {
+        println!("Day {:<2}", day);
+    }
+
+    // The last day of each event only has one part.
+    let part2 = part2.filter(|_| !is_last_day(day, options.event_days));
+
+    if let Some(runs) = options.bench {
+        return bench_day(
+            options,
+            runs,
+            year,
+            day,
+            input,
+            input_generator,
+            part1,
+            part2,
+        );
+    }
+
+    let now = Instant::now();
+    let mut input = input_generator(input);
+    let input = &mut input;
+    let parse_time = now.elapsed();
+    if table {
+        println!("     - Parsing input");
+        println!("       Took {:.3?}", parse_time);
+        println!();
+    }
+
+    let mut report = DayReport {
+        day: day.to_string(),
+        parse_time,
+        parts: Vec::new(),
+    };
+
+    let now = Instant::now();
+    let part1_solution = part1(input);
+    let elapsed = now.elapsed();
+    report.parts.push(check_part(
+        options,
+        year,
+        day,
+        1,
+        part1_solution.to_string(),
+        elapsed,
+    )?);
+    if table {
+        let part = &report.parts[0];
+        println!("     - Part 1: {}{}", part.answer, part.status.mark());
+        println!("       Took {:.3?}", elapsed);
+        println!();
+    }
+
+    if options.submit == Some(1) {
+        submit_answer(
+            year,
+            day,
+            1,
+            &report.parts[0].answer,
+            options.config,
+            options.profile,
+        )?;
+        eprintln!();
+    }
+
+    if let Some(part2) = part2 {
+        let now = Instant::now();
+        let part2_solution = part2(input);
+        let elapsed = now.elapsed();
+        let part2_solution = match part2_solution.answer() {
+            Some(answer) => answer,
+            None => {
+                report_missing_part2(options, day);
+                return Ok(report);
+            }
+        };
+        report
+            .parts
+            .push(check_part(options, year, day, 2, part2_solution, elapsed)?);
+        if table {
+            let part = &report.parts[1];
+            println!("     - Part 2: {}{}", part.answer, part.status.mark());
+            println!("       Took {:.3?}", elapsed);
+            println!();
+        }
+
+        if options.submit == Some(2) {
+            submit_answer(
+                year,
+                day,
+                2,
+                &report.parts[1].answer,
+                options.config,
+                options.profile,
+            )?;
+            eprintln!();
+        }
+    }
+
+    Ok(report)
+}
+
+fn check_part(
+    options: &RunOptions,
+    year: i32,
+    day: &str,
+    part: u32,
+    answer: String,
+    time: Duration,
+) -> Result<PartReport> {
+    let status = match options.input {
+        InputSource::Puzzle => {
+            answer_status(year, day, part, &answer, options.config, options.profile)
+        }
+        InputSource::Example(example) => example_status(
+            year,
+            day,
+            example,
+            part,
+            &answer,
+            options.config,
+            options.profile,
+        ),
+        InputSource::File(_) | InputSource::Stdin => Status::Unknown,
+    };
+    Ok(PartReport {
+        part,
+        answer,
+        time,
+        status,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn bench_day<'i, I, P1: Display, P2: PartAnswer>(
+    options: &RunOptions,
+    runs: usize,
+    year: i32,
+    day: &str,
+    input: &'i str,
+    input_generator: impl Fn(&'i str) -> I,
+    part1: impl Fn(&mut I) -> P1,
+    part2: Option<impl Fn(&mut I) -> P2>,
+) -> Result<DayReport> {
+    let table = options.format == Format::Table;
+
+    let (_, stats) = bench(runs, || (), |()| input_generator(input));
+    if table {
+        println!("     - Parsing input");
+        println!("       {}", stats);
+        println!();
+    }
+
+    let mut report = DayReport {
+        day: day.to_string(),
+        parse_time: stats.median,
+        parts: Vec::new(),
+    };
+
+    let ((part1_solution, _), stats) = bench(
+        runs,
+        || input_generator(input),
+        |mut input| (part1(&mut input), input),
+    );
+    report.parts.push(check_part(
+        options,
+        year,
+        day,
+        1,
+        part1_solution.to_string(),
+        stats.median,
+    )?);
+    if table {
+        let part = &report.parts[0];
+        println!("     - Part 1: {}{}", part.answer, part.status.mark());
+        println!("       {}", stats);
+        println!();
+    }
+
+    if let Some(part2) = part2 {
+        let ((part2_solution, _), stats) = bench(
+            runs,
+            || input_generator(input),
+            |mut input| (part2(&mut input), input),
+        );
+        let Some(answer) = part2_solution.answer() else {
+            report_missing_part2(options, day);
+            return Ok(report);
+        };
+        report
+            .parts
+            .push(check_part(options, year, day, 2, answer, stats.median)?);
+        if table {
+            let part = &report.parts[1];
+            println!("     - Part 2: {}{}", part.answer, part.status.mark());
+            println!("       {}", stats);
+            println!();
+        }
+    }
+
+    Ok(report)
+}
+
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:.3?}, median {:.3?}, mean {:.3?}, stddev {:.3?}",
+            self.min, self.median, self.mean, self.stddev
+        )
+    }
+}
+
+// The input of every run is built by `setup` outside of the measured time, so solutions
+// that mutate their input always start from a fresh one. The result of the last run is
+// returned, everything else is dropped outside of the measured time too.
+fn bench<S, T>(runs: usize, mut setup: impl FnMut() -> S, mut f: impl FnMut(S) -> T) -> (T, Stats) {
+    let mut times = Vec::with_capacity(runs);
+    let mut last = None;
+    for _ in 0..runs {
+        let state = setup();
+        let now = Instant::now();
+        let result = f(state);
+        times.push(now.elapsed());
+        last = Some(result);
+    }
+    times.sort_unstable();
+
+    let secs = times.iter().map(Duration::as_secs_f64).collect::<Vec<_>>();
+    let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+    let variance = secs.iter().map(|s| (s - mean) * (s - mean)).sum::<f64>() / secs.len() as f64;
+
+    let stats = Stats {
+        min: times[0],
+        median: times[times.len() / 2],
+        mean: Duration::from_secs_f64(mean),
+        stddev: Duration::from_secs_f64(variance.sqrt()),
+    };
+    (last.unwrap(), stats)
+}
@@ -0,0 +1,160 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Duration, Local};
+use regex::Regex;
+
+use super::error::{AocError, Result};
+use super::http;
+use super::storage::ensure_gitignored;
+
+// AoC doesn't tell how long a session lasts, in practice it's about a month.
+const SESSION_LIFETIME_DAYS: i64 = 30;
+
+fn session_file(profile: Option<&str>) -> Result<PathBuf> {
+    let dirs = match directories::ProjectDirs::from("com.github", "Giuschi", "Aoc-Session") {
+        Some(dirs) => dirs,
+        None => {
+            let error =
+                std::io::Error::new(std::io::ErrorKind::NotFound, "no valid home directory");
+            return Err(AocError::io("Couldn't find the config directory")(error));
+        }
+    };
+    let mut session_file = dirs.config_dir().as_os_str().to_owned();
+    if let Some(profile) = profile {
+        session_file.push(format!("-{}", profile));
+    }
+    Ok(session_file.into())
+}
+
+pub fn set_session(session: &str, profile: Option<&str>) -> Result<()> {
+    let session_file = session_file(profile)?;
+    if let Some(parent) = session_file.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(AocError::io("Couldn't create parent directories"))?;
+    }
+    let mut session_file = std::fs::File::create(&session_file)
+        .map_err(AocError::io("Couldn't create config file"))?;
+    write!(session_file, "{}", session).map_err(AocError::io("Couldn't write to config file"))
+}
+
+enum Source {
+    Env(String),
+    File(PathBuf),
+}
+
+// With a profile the env var and the local files become `AOC_SESSION_$PROFILE`
+// and `.aoc-session-$PROFILE`, so each profile can be set separately.
+fn session_var(profile: Option<&str>) -> String {
+    match profile {
+        Some(profile) => format!("AOC_SESSION_{}", profile.to_uppercase().replace('-', "_")),
+        None => "AOC_SESSION".to_string(),
+    }
+}
+
+fn find_session(src_dir: &str, profile: Option<&str>) -> Result<(String, Source)> {
+    let var = session_var(profile);
+    if let Ok(session) = std::env::var(&var) {
+        if !session.trim().is_empty() {
+            return Ok((session.trim().to_string(), Source::Env(var)));
+        }
+    }
+
+    let local_name = match profile {
+        Some(profile) => format!(".aoc-session-{}", profile),
+        None => ".aoc-session".to_string(),
+    };
+    let local_file = format!("{}/{}", src_dir, local_name);
+    if let Some(session) = read_trimmed(local_file.as_ref())? {
+        ensure_gitignored(src_dir, &format!("/{}", local_name))?;
+        return Ok((session, Source::File(local_file.into())));
+    }
+
+    let env_file = format!("{}/.env", src_dir);
+    if let Some(env) = read_trimmed(env_file.as_ref())? {
+        if let Some(session) = env.lines().find_map(|line| parse_env_line(line, &var)) {
+            ensure_gitignored(src_dir, "/.env")?;
+            return Ok((session, Source::File(env_file.into())));
+        }
+    }
+
+    let session_file = session_file(profile)?;
+    match read_trimmed(&session_file)? {
+        Some(session) => Ok((session, Source::File(session_file))),
+        None => Err(AocError::MissingSession),
+    }
+}
+
+fn read_trimmed(path: &Path) -> Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(session) if !session.trim().is_empty() => Ok(Some(session.trim().to_string())),
+        Ok(_) => Ok(None),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(AocError::io(format!("Couldn't open {}", path.display()))(
+            error,
+        )),
+    }
+}
+
+fn parse_env_line(line: &str, var: &str) -> Option<String> {
+    let line = line.trim();
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let (key, value) = line.split_once('=')?;
+    if key.trim() != var {
+        return None;
+    }
+    let value = value.trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .or_else(|| {
+            value
+                .strip_prefix('\'')
+                .and_then(|value| value.strip_suffix('\''))
+        })
+        .unwrap_or(value);
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+pub fn get_session(src_dir: &str, profile: Option<&str>) -> Result<String> {
+    find_session(src_dir, profile).map(|(session, _)| session)
+}
+
+pub fn check_session(src_dir: &str, profile: Option<&str>) -> Result<()> {
+    let (session, source) = find_session(src_dir, profile)?;
+    let agent = http::create_agent(session)?;
+    let url = http::url("/settings");
+    let body = http::get(&agent, &url)?;
+
+    // Logged out pages show a login link where the user name would be.
+    let user_re = Regex::new(r#"<div class="user">([^<]*)"#).unwrap();
+    let user = user_re
+        .captures(&body)
+        .map(|caps| super::puzzle::decode_entities(caps[1].trim()))
+        .ok_or(AocError::ExpiredSession)?;
+    println!("The session token is valid, logged in as {}", user);
+
+    let path = match source {
+        Source::Env(var) => {
+            println!(
+                "It was read from ${}, so it's not known when it expires",
+                var
+            );
+            return Ok(());
+        }
+        Source::File(path) => path,
+    };
+    let set_at = std::fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .unwrap_or_else(|_| SystemTime::now());
+    let set_at = DateTime::<Local>::from(set_at);
+    let expires_at = set_at + Duration::days(SESSION_LIFETIME_DAYS);
+    println!(
+        "It was read from {}, set on {} and should expire around {}",
+        path.display(),
+        set_at.format("%Y-%m-%d"),
+        expires_at.format("%Y-%m-%d")
+    );
+    Ok(())
+}
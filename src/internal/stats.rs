@@ -0,0 +1,97 @@
+use regex::Regex;
+use serde::Serialize;
+
+use super::config::Config;
+use super::error::{AocError, Result};
+use super::http;
+use super::puzzle::decode_entities;
+use super::report::Format;
+
+#[derive(Serialize)]
+struct DayStats {
+    day: u32,
+    part1: PartStats,
+    part2: Option<PartStats>,
+}
+
+// `score` is missing for the years without a global leaderboard.
+#[derive(Serialize)]
+struct PartStats {
+    time: String,
+    rank: u32,
+    score: Option<u32>,
+}
+
+pub fn show_stats(year: i32, format: Format, config: &Config, profile: Option<&str>) -> Result<()> {
+    let agent = http::create_agent(super::get_session(config.src_dir(), profile)?)?;
+    let url = http::url(&format!("/{}/leaderboard/self", year));
+    let body = http::get(&agent, &url)?;
+
+    let pre_re = Regex::new(r"(?s)<pre>(.*?)</pre>").unwrap();
+    let stats = match pre_re.captures(&body) {
+        Some(pre) => parse_stats(&pre[1]).ok_or(AocError::UnexpectedResponse { url })?,
+        None if body.contains("<article") => Vec::new(),
+        None => return Err(AocError::UnexpectedResponse { url }),
+    };
+
+    match format {
+        Format::Json => println!("{}", serde_json::to_string(&stats).unwrap()),
+        _ if stats.is_empty() => println!("No stars collected in {} yet", year),
+        _ => {
+            println!(
+                "{:>3}  {:>10} {:>7} {:>5}  {:>10} {:>7} {:>5}",
+                "Day", "Time 1", "Rank 1", "Score", "Time 2", "Rank 2", "Score"
+            );
+            for day in &stats {
+                print!("{:>3}  {}", day.day, part_row(Some(&day.part1)));
+                println!("  {}", part_row(day.part2.as_ref()));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn part_row(part: Option<&PartStats>) -> String {
+    match part {
+        Some(part) => format!(
+            "{:>10} {:>7} {:>5}",
+            part.time,
+            part.rank,
+            part.score
+                .map_or_else(|| "-".to_string(), |score| score.to_string())
+        ),
+        None => format!("{:>10} {:>7} {:>5}", "-", "-", "-"),
+    }
+}
+
+// Each row is the day followed by time, rank and score of both parts,
+// where the part 2 columns are `-` if it hasn't been solved yet.
+fn parse_stats(pre: &str) -> Option<Vec<DayStats>> {
+    let tag_re = Regex::new(r"<[^>]*>").unwrap();
+    let text = decode_entities(&tag_re.replace_all(pre, ""));
+
+    let mut stats = text
+        .lines()
+        .filter(|line| line.trim_start().starts_with(|c: char| c.is_ascii_digit()))
+        .map(|line| {
+            let columns = line.split_whitespace().collect::<Vec<_>>();
+            let (day, parts) = columns.split_first()?;
+            let (part1, part2) = parts.split_at(parts.len() / 2);
+            Some(DayStats {
+                day: day.parse().ok()?,
+                part1: parse_part(part1)?,
+                part2: parse_part(part2),
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    stats.sort_by_key(|day| day.day);
+    Some(stats)
+}
+
+fn parse_part(columns: &[&str]) -> Option<PartStats> {
+    Some(PartStats {
+        time: columns.first()?.to_string(),
+        rank: columns.get(1)?.parse().ok()?,
+        score: columns.get(2).and_then(|score| score.parse().ok()),
+    })
+}
@@ -0,0 +1,259 @@
+use std::path::Path;
+
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+use super::calendar::days_in_year;
+use super::config::Config;
+use super::error::{AocError, Result};
+use super::Year;
+
+// Encrypted inputs are stored next to where the plain ones would be, with an `.enc` suffix,
+// as the magic bytes followed by the salt, the nonce and the ciphertext. The inputs can end
+// up in public repositories, so the key is derived from the passphrase with Argon2id and
+// a random salt for each file to make guessing the passphrase expensive.
+const MAGIC: &[u8] = b"AOCENC2";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KEY_FILE: &str = ".aoc-key";
+
+enum KeySource {
+    Env,
+    File,
+}
+
+pub struct InputKey {
+    passphrase: String,
+    source: KeySource,
+}
+
+impl InputKey {
+    fn cipher(&self, salt: &[u8]) -> Result<ChaCha20Poly1305> {
+        let mut key = Key::default();
+        Argon2::default()
+            .hash_password_into(self.passphrase.as_bytes(), salt, &mut key)
+            .map_err(|error| {
+                AocError::InvalidArgument(format!("Couldn't derive the input key: {}", error))
+            })?;
+        Ok(ChaCha20Poly1305::new(&key))
+    }
+
+    fn encrypt(&self, input: &str, path: &str) -> Result<Vec<u8>> {
+        let mut salt = [0; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher(&salt)?
+            .encrypt(&nonce, input.as_bytes())
+            .map_err(|_| AocError::InvalidArgument(format!("Couldn't encrypt {}", path)))?;
+        Ok([MAGIC, &salt, &nonce, &ciphertext].concat())
+    }
+
+    fn decrypt(&self, data: &[u8], path: &str) -> Result<String> {
+        let invalid = || AocError::InvalidFile(path.to_string());
+        let data = data.strip_prefix(MAGIC).ok_or_else(invalid)?;
+        if data.len() < SALT_LEN + NONCE_LEN {
+            return Err(invalid());
+        }
+        let (salt, data) = data.split_at(SALT_LEN);
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let input = self
+            .cipher(salt)?
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                AocError::InvalidArgument(format!("Couldn't decrypt {}, wrong input key?", path))
+            })?;
+        String::from_utf8(input).map_err(|_| invalid())
+    }
+}
+
+// The key is derived from the passphrase in `AOC_INPUT_KEY` or in the `.aoc-key` file
+// of the crate. Without one the inputs are stored in plain text.
+pub fn input_key(src_dir: &str) -> Result<Option<InputKey>> {
+    let (passphrase, source) = match std::env::var("AOC_INPUT_KEY") {
+        Ok(passphrase) if !passphrase.trim().is_empty() => (passphrase, KeySource::Env),
+        _ => match std::fs::read_to_string(format!("{}/{}", src_dir, KEY_FILE)) {
+            Ok(passphrase) if !passphrase.trim().is_empty() => (passphrase, KeySource::File),
+            Ok(_) => return Ok(None),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(AocError::io("Couldn't read the input key")(error)),
+        },
+    };
+    Ok(Some(InputKey {
+        passphrase: passphrase.trim().to_string(),
+        source,
+    }))
+}
+
+pub fn input_exists(path: &str) -> bool {
+    Path::new(path).exists() || Path::new(&format!("{}.enc", path)).exists()
+}
+
+pub fn read_input(path: &str, src_dir: &str) -> Result<Option<String>> {
+    if let Ok(input) = std::fs::read_to_string(path) {
+        return Ok(Some(input));
+    }
+
+    let encrypted_path = format!("{}.enc", path);
+    let data = match std::fs::read(&encrypted_path) {
+        Ok(data) => data,
+        Err(_) => return Ok(None),
+    };
+    let key = input_key(src_dir)?.ok_or_else(|| {
+        AocError::InvalidArgument(format!(
+            "{} is encrypted, set $AOC_INPUT_KEY or create {} to read it",
+            encrypted_path, KEY_FILE
+        ))
+    })?;
+    key.decrypt(&data, &encrypted_path).map(Some)
+}
+
+pub fn write_input(path: &str, config: &Config, input: &str) -> Result<()> {
+    let src_dir = config.src_dir();
+    if let Some(parent) = Path::new(path).parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(AocError::io("Couldn't create parent directories"))?;
+    }
+
+    match input_key(src_dir)? {
+        Some(key) => {
+            if let KeySource::File = key.source {
+                ensure_gitignored(src_dir, &format!("/{}", KEY_FILE))?;
+            }
+            gitignore_inputs(config, true)?;
+            let encrypted_path = format!("{}.enc", path);
+            std::fs::write(&encrypted_path, key.encrypt(input, &encrypted_path)?)
+                .map_err(AocError::io("Couldn't write input file"))
+        }
+        None => {
+            gitignore_inputs(config, false)?;
+            std::fs::write(path, input).map_err(AocError::io("Couldn't write input file"))
+        }
+    }
+}
+
+fn read_gitignore(path: &str) -> Result<String> {
+    match std::fs::read_to_string(path) {
+        Ok(gitignore) => Ok(gitignore),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(error) => Err(AocError::io("Couldn't read .gitignore")(error)),
+    }
+}
+
+// AoC asks not to publish the inputs, and the session token gives access to the account,
+// so make sure neither can be committed by mistake.
+pub fn ensure_gitignored(src_dir: &str, entry: &str) -> Result<()> {
+    let path = format!("{}/.gitignore", src_dir);
+    let gitignore = read_gitignore(&path)?;
+    let name = entry.trim_matches('/');
+    if gitignore
+        .lines()
+        .any(|line| line.trim().trim_matches('/') == name)
+    {
+        return Ok(());
+    }
+
+    let separator = if gitignore.is_empty() || gitignore.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    std::fs::write(&path, format!("{}{}{}\n", gitignore, separator, entry))
+        .map_err(AocError::io("Couldn't update .gitignore"))?;
+    eprintln!("Added {} to {}", entry, path);
+    Ok(())
+}
+
+// The input directory is ignored as a whole, unless inputs were encrypted: then everything in
+// it but the encrypted inputs is ignored, so they can be committed.
+fn gitignore_inputs(config: &Config, encrypted: bool) -> Result<()> {
+    let dir = match config.local_input_dir() {
+        Some(dir) => dir,
+        None => return Ok(()),
+    };
+    let src_dir = config.src_dir();
+    let path = format!("{}/.gitignore", src_dir);
+    let gitignore = read_gitignore(&path)?;
+    let rules = [
+        format!("/{}/**", dir),
+        format!("!/{}/**/", dir),
+        format!("!/{}/**/*.enc", dir),
+    ];
+    if rules
+        .iter()
+        .all(|rule| gitignore.lines().any(|line| line.trim() == rule))
+    {
+        return Ok(());
+    }
+    if !encrypted {
+        return ensure_gitignored(src_dir, &format!("/{}/", dir));
+    }
+
+    // Ignoring the whole directory would ignore the encrypted inputs too.
+    let mut lines = gitignore
+        .lines()
+        .filter(|line| {
+            line.trim().trim_matches('/') != dir && !rules.iter().any(|rule| line.trim() == rule)
+        })
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    lines.extend(rules);
+    std::fs::write(&path, lines.join("\n") + "\n")
+        .map_err(AocError::io("Couldn't update .gitignore"))?;
+    eprintln!(
+        "Updated {} to ignore everything in /{}/ but the encrypted inputs",
+        path, dir
+    );
+    Ok(())
+}
+
+// Without `-y` the inputs of all the years registered in `main!` are encrypted.
+pub fn encrypt_inputs(
+    years: &[Year],
+    selected: Option<i32>,
+    config: &Config,
+    profile: Option<&str>,
+) -> Result<()> {
+    let src_dir = config.src_dir();
+    let key = input_key(src_dir)?.ok_or_else(|| {
+        AocError::InvalidArgument(format!(
+            "Set $AOC_INPUT_KEY or create {} to encrypt the inputs",
+            KEY_FILE
+        ))
+    })?;
+    if let KeySource::File = key.source {
+        ensure_gitignored(src_dir, &format!("/{}", KEY_FILE))?;
+    }
+    gitignore_inputs(config, true)?;
+
+    let years = match selected {
+        Some(year) => {
+            let event_days = years
+                .iter()
+                .find(|y| y.year == year)
+                .map_or_else(|| days_in_year(year), |y| y.event_days);
+            vec![(year, event_days)]
+        }
+        None => years.iter().map(|y| (y.year, y.event_days)).collect(),
+    };
+    for (year, event_days) in years {
+        let mut encrypted = 0;
+        for day in 1..=event_days {
+            let path = config.input_path(profile, year, &day.to_string());
+            let input = match std::fs::read_to_string(&path) {
+                Ok(input) => input,
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(error) => return Err(AocError::io(format!("Couldn't read {}", path))(error)),
+            };
+            let encrypted_path = format!("{}.enc", path);
+            std::fs::write(&encrypted_path, key.encrypt(&input, &encrypted_path)?)
+                .map_err(AocError::io("Couldn't write input file"))?;
+            std::fs::remove_file(&path).map_err(AocError::io("Couldn't remove input file"))?;
+            encrypted += 1;
+        }
+        eprintln!("Encrypted {} inputs of {}", encrypted, year);
+    }
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use std::fmt;
+use std::time::Duration;
+
+use regex::Regex;
+
+use super::answers::Answers;
+use super::config::Config;
+use super::error::{AocError, Result};
+use super::http;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited(Duration),
+    AlreadySolved,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::TooHigh => write!(f, "That's not the right answer, your answer is too high"),
+            Verdict::TooLow => write!(f, "That's not the right answer, your answer is too low"),
+            Verdict::Wrong => write!(f, "That's not the right answer"),
+            Verdict::RateLimited(wait) => {
+                write!(
+                    f,
+                    "You gave an answer too recently, wait {:?} before retrying",
+                    wait
+                )
+            }
+            Verdict::AlreadySolved => write!(f, "This part is already solved or still locked"),
+        }
+    }
+}
+
+pub fn submit_answer(
+    year: i32,
+    day: &str,
+    part: u32,
+    answer: &str,
+    config: &Config,
+    profile: Option<&str>,
+) -> Result<Verdict> {
+    let mut answers = Answers::load(year, day, config, profile)?;
+    if let Some(verdict) = answers.check(part, answer) {
+        eprintln!(
+            "     - Not submitting {} for day {} part {}, already known: {}",
+            answer, day, part, verdict
+        );
+        return Ok(verdict);
+    }
+
+    let agent = http::create_agent(super::get_session(config.src_dir(), profile)?)?;
+    let verdict = post_answer(&agent, year, day, part, answer)?;
+    answers.record(part, answer, verdict)?;
+    Ok(verdict)
+}
+
+fn post_answer(
+    agent: &ureq::Agent,
+    year: i32,
+    day: &str,
+    part: u32,
+    answer: &str,
+) -> Result<Verdict> {
+    eprint!(
+        "     - Submitting {} for day {} part {}... ",
+        answer, day, part
+    );
+
+    let url = http::url(&format!("/{}/day/{}/answer", year, day));
+    let level = part.to_string();
+    let body = http::post_form(agent, &url, &[("level", &level), ("answer", answer)])?;
+
+    let verdict = parse_verdict(&body).ok_or(AocError::UnexpectedResponse { url })?;
+    eprintln!("{}", verdict);
+    Ok(verdict)
+}
+
+fn parse_verdict(body: &str) -> Option<Verdict> {
+    let verdict = if body.contains("That's the right answer") {
+        Verdict::Correct
+    } else if body.contains("That's not the right answer") {
+        if body.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if body.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if body.contains("You gave an answer too recently") {
+        let wait_re = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        let wait = wait_re.captures(body).map_or(0, |caps| {
+            let minutes = caps
+                .get(1)
+                .map_or(0, |m| m.as_str().parse::<u64>().unwrap());
+            let seconds = caps[2].parse::<u64>().unwrap();
+            minutes * 60 + seconds
+        });
+        Verdict::RateLimited(Duration::from_secs(wait))
+    } else if body.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        return None;
+    };
+    Some(verdict)
+}
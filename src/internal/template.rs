@@ -0,0 +1,57 @@
+use regex::Regex;
+
+use super::config::Config;
+use super::error::{AocError, Result};
+use super::puzzle::decode_entities;
+use super::{get_description, get_example_input};
+
+static DEFAULT_TEMPLATE: &str = include_str!("../../template.rs");
+
+// Templates are looked up in the template directory of the crate as `$NAME.rs`, and
+// `default.rs` replaces the built-in template when no name is given.
+fn load_template(name: Option<&str>, config: &Config) -> Result<String> {
+    let dir = config.template_dir();
+    let path = format!("{}/{}.rs", dir, name.unwrap_or("default"));
+    match std::fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(_) if name.is_none() => Ok(DEFAULT_TEMPLATE.to_string()),
+        Err(_) => Err(AocError::InvalidArgument(format!(
+            "No template {} found in {}",
+            name.unwrap(),
+            dir
+        ))),
+    }
+}
+
+// The description is only downloaded if the template needs the title or the example.
+pub fn render_template(
+    name: Option<&str>,
+    year: i32,
+    day: &str,
+    config: &Config,
+    profile: Option<&str>,
+) -> Result<String> {
+    let mut template = load_template(name, config)?
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day}}", day);
+
+    if template.contains("{{title}}") {
+        let description = get_description(year, day, config, profile)?;
+        let title_re = Regex::new(r"<h2>--- Day \d+: (.*?) ---</h2>").unwrap();
+        let title = title_re
+            .captures(&description)
+            .map_or_else(String::new, |caps| decode_entities(&caps[1]));
+        template = template.replace("{{title}}", &title);
+    }
+
+    if template.contains("{{example}}") {
+        let example = match get_example_input(year, day, 1, config, profile) {
+            Ok(example) => example,
+            Err(AocError::InvalidArgument(_)) => String::new(),
+            Err(error) => return Err(error),
+        };
+        template = template.replace("{{example}}", example.trim_end_matches('\n'));
+    }
+
+    Ok(template)
+}
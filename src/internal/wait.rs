@@ -0,0 +1,69 @@
+use std::io::Write;
+use std::time::Duration;
+
+use chrono::Utc;
+
+use super::calendar::next_unlock;
+use super::config::Config;
+use super::error::{AocError, Result};
+use super::http;
+
+const MAX_ATTEMPTS: u32 = 6;
+
+pub fn wait_and_download(
+    year: i32,
+    event_days: u32,
+    config: &Config,
+    profile: Option<&str>,
+) -> Result<u32> {
+    let (day, unlock) = next_unlock(year, event_days).ok_or_else(|| {
+        AocError::InvalidArgument(format!("All the puzzles of {} are already unlocked", year))
+    })?;
+
+    // Check the session before waiting, so a missing one doesn't show up only at the end.
+    let agent = http::create_agent(super::get_session(config.src_dir(), profile)?)?;
+
+    loop {
+        let remaining = (unlock.with_timezone(&Utc) - Utc::now()).num_milliseconds();
+        if remaining <= 0 {
+            break;
+        }
+        let seconds = (remaining + 999) / 1000;
+        eprint!(
+            "\rDay {} unlocks in {:02}:{:02}:{:02} ",
+            day,
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        );
+        std::io::stderr().flush().ok();
+        let millis = match remaining % 1000 {
+            0 => 1000,
+            millis => millis,
+        };
+        std::thread::sleep(Duration::from_millis(millis as u64));
+    }
+    eprintln!();
+
+    let day_str = day.to_string();
+    let mut delay = Duration::from_secs(1);
+    for attempt in 1.. {
+        match super::download_input(&agent, year, &day_str, config, profile) {
+            Err(error) if attempt < MAX_ATTEMPTS && is_transient(&error) => {
+                eprintln!("{}, retrying in {:?}", error, delay);
+                std::thread::sleep(delay);
+                delay *= 2;
+            }
+            result => return result.map(|_| day),
+        }
+    }
+    unreachable!()
+}
+
+fn is_transient(error: &AocError) -> bool {
+    match error {
+        AocError::NotUnlocked | AocError::Network { .. } => true,
+        AocError::Http { status, .. } => *status >= 500,
+        _ => false,
+    }
+}
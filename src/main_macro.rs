@@ -1,69 +1,166 @@
 #[macro_export]
 macro_rules! main {
-    ($year:literal => $($d:ident),* $(,)?) => {
+    ($year:literal $(, $event_days:literal days)? => $($d:ident),* $(,)?) => {
         pub use aoc_helper::prelude;
         pub use aoc_helper::parse_display;
+
+        $crate::main!(@YEAR $year, $($event_days)? => $($d),*);
+
+        fn main() {
+            let src_dir = env!("CARGO_MANIFEST_DIR");
+            let years = &[aoc_helper::internal::Year {
+                year: YEAR,
+                module: None,
+                days: DAYS,
+                event_days: EVENT_DAYS,
+            }];
+
+            let result = aoc_helper::internal::run_clap(years, src_dir, |options| {
+                let mut reports = Vec::new();
+                run_days(&options, src_dir, &mut reports)?;
+                aoc_helper::internal::print_reports(&options, src_dir, &reports)
+            });
+            aoc_helper::internal::exit_on_error(result);
+        }
+    };
+    ($($y:ident $(, $event_days:literal days)? => $($d:ident),* $(,)?);+ $(;)?) => {
+        pub use aoc_helper::prelude;
+        pub use aoc_helper::parse_display;
+
+        $(
+            pub mod $y {
+                pub use aoc_helper::prelude;
+                pub use aoc_helper::parse_display;
+
+                $crate::main!(
+                    @YEAR aoc_helper::internal::parse_year(stringify!($y)), $($event_days)? => $($d),*
+                );
+            }
+        )+
+
+        fn main() {
+            let src_dir = env!("CARGO_MANIFEST_DIR");
+            let years = &[$(
+                aoc_helper::internal::Year {
+                    year: $y::YEAR,
+                    module: Some(stringify!($y)),
+                    days: $y::DAYS,
+                    event_days: $y::EVENT_DAYS,
+                }
+            ),+];
+
+            let result = aoc_helper::internal::run_clap(years, src_dir, |options| {
+                let mut reports = Vec::new();
+                $(
+                    if options.year == $y::YEAR {
+                        $y::run_days(&options, src_dir, &mut reports)?;
+                    }
+                )+
+                aoc_helper::internal::print_reports(&options, src_dir, &reports)
+            });
+            aoc_helper::internal::exit_on_error(result);
+        }
+    };
+    (@YEAR $year:expr, $($event_days:literal)? => $($d:ident),*) => {
         $(
             #[allow(clippy::ptr_arg)]
             mod $d;
         )*
 
-        fn main() {
-            let src_dir = env!("CARGO_MANIFEST_DIR");
+        #[allow(unused_imports)]
+        use aoc_helper::internal::missing_part2 as part2;
+
+        pub const YEAR: i32 = $year;
+        pub const DAYS: &[&str] = &[$( stringify!($d) ),*];
+        pub const EVENT_DAYS: u32 = $crate::main!(@EVENT_DAYS YEAR $(, $event_days)?);
 
-			let days: &[&str] = &[$( stringify!($d) ),*];
-			let default_day = days.last().copied();
+        pub fn run_days(
+            options: &aoc_helper::internal::RunOptions,
+            src_dir: &str,
+            reports: &mut Vec<aoc_helper::internal::DayReport>,
+        ) -> aoc_helper::internal::Result<()> {
+            let requested_day = options.day;
 
-            aoc_helper::internal::run_clap($year, default_day.map(|s| &s[3..]), src_dir, |requested_day| {
-                let mut total = ::std::time::Duration::default();
-                let mut found = false;
+            $(
+                if requested_day == Some(&stringify!($d)[3..]) || requested_day == Some("all") {
+                    const DAY: &str = stringify!($d);
+                    let input = aoc_helper::internal::load_input(options, YEAR, &DAY[3..])?;
+                    reports.push(aoc_helper::internal::run_day(
+                        options,
+                        YEAR,
+                        &DAY[3..],
+                        &input,
+                        $d::input_generator,
+                        |input| $d::part1(input),
+                        Some({
+                            #[allow(unused_imports)]
+                            use $d::*;
+                            |input: &mut _| part2(input)
+                        }),
+                    )?);
+                }
+            )*
+
+            Ok(())
+        }
 
-				$(
-					if requested_day == Some(&stringify!($d)[3..]) || requested_day == Some("all") {
-						found = true;
+        #[cfg(test)]
+        mod examples {
+            #[allow(dead_code)]
+            const EXAMPLES: &[aoc_helper::internal::Example] = &[];
+            #[allow(unused_imports)]
+            use aoc_helper::internal::missing_part2 as part2;
 
-						const DAY: &str = stringify!($d);
-						println!("Day {:<2}", &DAY[3..]);
-						let input = aoc_helper::internal::get_input($year, &DAY[3..], src_dir);
+            $(
+                #[test]
+                fn $d() {
+                    #[allow(unused_imports)]
+                    use super::$d::*;
 
-						let now = std::time::Instant::now();
-						let mut input = $d::input_generator(&input);
+                    for example in EXAMPLES {
+                        let mut input = super::$d::input_generator(example.input);
                         let input = &mut input;
-						let elapsed = now.elapsed();
-						total += elapsed;
-						println!("     - Parsing input");
-						println!("       Took {:.3?}", elapsed);
-						println!();
-
-						let now = std::time::Instant::now();
-						let part1_solution = $d::part1(input);
-						let elapsed = now.elapsed();
-						total += elapsed;
-						println!("     - Part 1: {}", part1_solution);
-						println!("       Took {:.3?}", elapsed);
-						println!();
-
-						$crate::main!(@PART2 input total $d);
-					}
-				)*
-
-                if found {
-                    println!("Took in total: {:.3?}", total);
-                } else {
-                    println!("No matching day was found")
+                        let answer = match example.part {
+                            1 => super::$d::part1(input).to_string(),
+                            _ if aoc_helper::internal::is_last_day(&stringify!($d)[3..], super::EVENT_DAYS) => {
+                                panic!("The last day has no part 2")
+                            }
+                            _ => aoc_helper::internal::PartAnswer::answer(&part2(input))
+                                .expect("part2 is not implemented"),
+                        };
+                        assert_eq!(
+                            answer,
+                            example.answer.to_string(),
+                            "Wrong answer for part {} of the example:\n{}",
+                            example.part,
+                            example.input
+                        );
+                    }
                 }
-                println!();
-            });
+            )*
         }
     };
-    (@PART2 $input:ident $total:ident day25) => {};
-    (@PART2 $input:ident $total:ident $d:ident) => {
-        let now = std::time::Instant::now();
-        let part2_solution = $d::part2($input);
-        let elapsed = now.elapsed();
-        $total += elapsed;
-        println!("     - Part 2: {}", part2_solution);
-        println!("       Took {:.3?}", elapsed);
-        println!();
+    (@EVENT_DAYS $year:ident) => {
+        aoc_helper::internal::days_in_year($year)
+    };
+    (@EVENT_DAYS $year:ident, $event_days:literal) => {{
+        assert!(matches!($event_days, 1..=25), "An event has between 1 and 25 days");
+        $event_days
+    }};
+}
+
+#[macro_export]
+macro_rules! examples {
+    ($($part:ident: $input:expr => $answer:expr),* $(,)?) => {
+        #[cfg(test)]
+        pub const EXAMPLES: &[aoc_helper::internal::Example] = &[$(
+            aoc_helper::internal::Example {
+                part: $crate::examples!(@PART $part),
+                input: $input,
+                answer: &$answer,
+            }
+        ),*];
     };
+    (@PART part1) => { 1 };
+    (@PART part2) => { 2 };
 }
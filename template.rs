@@ -13,3 +13,7 @@ pub fn part1(input: &Input) -> usize {
 pub fn part2(input: &Input) -> usize {
     0
 }
+
+aoc_helper::examples! {
+    // part1: "" => 0,
+}
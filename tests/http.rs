@@ -0,0 +1,309 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use aoc_helper::internal::{
+    answer_status, get_input, submit_answer, AocError, Config, Status, Verdict,
+};
+
+// A stand-in for adventofcode.com, shared by all the tests since the base URL and the
+// session are read from the environment. Each test uses its own year to not interfere.
+struct Mock {
+    dir: PathBuf,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl Mock {
+    fn get() -> &'static Mock {
+        static MOCK: OnceLock<Mock> = OnceLock::new();
+        MOCK.get_or_init(|| {
+            let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+            let addr = server.server_addr().to_ip().unwrap();
+            let dir = std::env::temp_dir().join(format!("aoc-helper-test-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+
+            std::env::set_var("AOC_BASE_URL", format!("http://{}", addr));
+            std::env::set_var("AOC_SESSION", "valid");
+            std::env::set_var("AOC_SESSION_EXPIRED", "expired");
+            std::env::remove_var("AOC_SESSION_MISSING");
+            std::env::remove_var("AOC_SESSION_LOCAL");
+            std::env::set_var("XDG_CONFIG_HOME", dir.join("config"));
+
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let log = requests.clone();
+            std::thread::spawn(move || {
+                for mut request in server.incoming_requests() {
+                    let mut body = String::new();
+                    request.as_reader().read_to_string(&mut body).unwrap();
+                    let cookie = header(&request, "Cookie");
+                    let user_agent = header(&request, "User-Agent");
+                    log.lock().unwrap().push(format!(
+                        "{} {} {}",
+                        request.method(),
+                        request.url(),
+                        user_agent
+                    ));
+
+                    let (status, response) = respond(request.url(), &cookie, &body);
+                    let response =
+                        tiny_http::Response::from_string(response).with_status_code(status);
+                    request.respond(response).unwrap();
+                }
+            });
+
+            Mock { dir, requests }
+        })
+    }
+
+    fn src_dir(&self, name: &str) -> String {
+        self.dir.join(name).to_str().unwrap().to_string()
+    }
+
+    fn requests(&self, path: &str) -> Vec<String> {
+        let requests = self.requests.lock().unwrap();
+        requests
+            .iter()
+            .filter(|request| request.split(' ').nth(1) == Some(path))
+            .cloned()
+            .collect()
+    }
+}
+
+fn config(src_dir: &str) -> Config {
+    Config::load(src_dir).unwrap()
+}
+
+fn header(request: &tiny_http::Request, name: &'static str) -> String {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.to_string())
+        .unwrap_or_default()
+}
+
+fn respond(url: &str, cookie: &str, body: &str) -> (u16, String) {
+    if cookie != "session=valid" {
+        return (
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".to_string(),
+        );
+    }
+
+    let parts = url.trim_start_matches('/').split('/').collect::<Vec<_>>();
+    match parts[..] {
+        ["2030", "day", _, "input"] => (
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!".to_string(),
+        ),
+        [year, "day", day, "input"] => (200, format!("input of {} day {}\n", year, day)),
+        [_, "day", _, "answer"] => {
+            let message = if body.contains("answer=42") {
+                "That's the right answer! You are one gold star closer."
+            } else if body.contains("answer=100") {
+                "That's not the right answer; your answer is too high."
+            } else if body.contains("answer=1&") || body.ends_with("answer=1") {
+                "That's not the right answer; your answer is too low."
+            } else {
+                "You gave an answer too recently. You have 1m 5s left to wait."
+            };
+            (
+                200,
+                format!("<main><article><p>{}</p></article></main>", message),
+            )
+        }
+        _ => (404, "404 Not Found".to_string()),
+    }
+}
+
+#[test]
+fn downloads_and_caches_the_input() {
+    let mock = Mock::get();
+    let src_dir = mock.src_dir("download");
+
+    let input = get_input(2015, "1", &config(&src_dir), None).unwrap();
+    assert_eq!(input, "input of 2015 day 1\n");
+    let cached = std::fs::read_to_string(format!("{}/input/2015/day1.txt", src_dir)).unwrap();
+    assert_eq!(cached, input);
+
+    let input = get_input(2015, "1", &config(&src_dir), None).unwrap();
+    assert_eq!(input, "input of 2015 day 1\n");
+    assert_eq!(mock.requests("/2015/day/1/input").len(), 1);
+
+    let gitignore = std::fs::read_to_string(format!("{}/.gitignore", src_dir)).unwrap();
+    assert_eq!(gitignore, "/input/\n");
+}
+
+#[test]
+fn stores_the_input_where_the_config_says() {
+    let mock = Mock::get();
+    let src_dir = mock.src_dir("config");
+    std::fs::create_dir_all(&src_dir).unwrap();
+    std::fs::write(
+        format!("{}/aoc.toml", src_dir),
+        "input-dir = \"data\"\ninput-file = \"{year}/{day:02}.in\"\n",
+    )
+    .unwrap();
+
+    let input = get_input(2014, "3", &config(&src_dir), None).unwrap();
+    assert_eq!(input, "input of 2014 day 3\n");
+    let cached = std::fs::read_to_string(format!("{}/data/2014/03.in", src_dir)).unwrap();
+    assert_eq!(cached, input);
+
+    let gitignore = std::fs::read_to_string(format!("{}/.gitignore", src_dir)).unwrap();
+    assert_eq!(gitignore, "/data/\n");
+}
+
+#[test]
+fn rejects_unknown_config_keys() {
+    let mock = Mock::get();
+    let src_dir = mock.src_dir("config-typo");
+    std::fs::create_dir_all(&src_dir).unwrap();
+    std::fs::write(format!("{}/aoc.toml", src_dir), "inputs-dir = \"data\"\n").unwrap();
+
+    let error = Config::load(&src_dir).err().unwrap();
+    assert!(matches!(error, AocError::InvalidFile(_)), "{:?}", error);
+    assert!(error.to_string().contains("inputs-dir"), "{}", error);
+}
+
+#[test]
+fn encrypts_the_input_with_the_key_file() {
+    let mock = Mock::get();
+    let src_dir = mock.src_dir("encrypted");
+    std::fs::create_dir_all(&src_dir).unwrap();
+    std::fs::write(format!("{}/.aoc-key", src_dir), "passphrase\n").unwrap();
+
+    let input = get_input(2021, "1", &config(&src_dir), None).unwrap();
+    assert_eq!(input, "input of 2021 day 1\n");
+    let path = format!("{}/input/2021/day1.txt", src_dir);
+    assert!(!std::path::Path::new(&path).exists());
+    let encrypted = std::fs::read(format!("{}.enc", path)).unwrap();
+    assert!(!String::from_utf8_lossy(&encrypted).contains("input of"));
+
+    let input = get_input(2021, "1", &config(&src_dir), None).unwrap();
+    assert_eq!(input, "input of 2021 day 1\n");
+    assert_eq!(mock.requests("/2021/day/1/input").len(), 1);
+
+    let gitignore = std::fs::read_to_string(format!("{}/.gitignore", src_dir)).unwrap();
+    assert_eq!(
+        gitignore,
+        "/.aoc-key\n/input/**\n!/input/**/\n!/input/**/*.enc\n"
+    );
+
+    std::fs::write(format!("{}/.aoc-key", src_dir), "wrong\n").unwrap();
+    let error = get_input(2021, "1", &config(&src_dir), None).unwrap_err();
+    assert!(matches!(error, AocError::InvalidArgument(_)), "{:?}", error);
+}
+
+#[test]
+fn identifies_itself_with_a_user_agent() {
+    let mock = Mock::get();
+    get_input(2016, "1", &config(&mock.src_dir("user-agent")), None).unwrap();
+
+    let requests = mock.requests("/2016/day/1/input");
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].contains("aoc-helper/"), "{}", requests[0]);
+}
+
+#[test]
+fn reports_an_expired_session() {
+    let mock = Mock::get();
+    let src_dir = mock.src_dir("expired");
+
+    let error = get_input(2017, "1", &config(&src_dir), Some("expired")).unwrap_err();
+    assert!(matches!(error, AocError::ExpiredSession), "{:?}", error);
+    assert_eq!(error.exit_code(), 4);
+    assert!(!std::path::Path::new(&format!("{}/input/expired/2017/day1.txt", src_dir)).exists());
+}
+
+#[test]
+fn reports_a_missing_session() {
+    let mock = Mock::get();
+
+    let error = get_input(
+        2018,
+        "1",
+        &config(&mock.src_dir("missing")),
+        Some("missing"),
+    )
+    .unwrap_err();
+    assert!(matches!(error, AocError::MissingSession), "{:?}", error);
+    assert_eq!(error.exit_code(), 3);
+    assert!(mock.requests("/2018/day/1/input").is_empty());
+}
+
+#[test]
+fn gitignores_the_local_session_file() {
+    let mock = Mock::get();
+    let src_dir = mock.src_dir("local-session");
+    std::fs::create_dir_all(&src_dir).unwrap();
+    std::fs::write(format!("{}/.aoc-session-local", src_dir), "valid\n").unwrap();
+
+    let input = get_input(2012, "1", &config(&src_dir), Some("local")).unwrap();
+    assert_eq!(input, "input of 2012 day 1\n");
+    let gitignore = std::fs::read_to_string(format!("{}/.gitignore", src_dir)).unwrap();
+    assert!(gitignore.lines().any(|line| line == "/.aoc-session-local"));
+}
+
+#[test]
+fn reports_a_locked_puzzle() {
+    let mock = Mock::get();
+
+    let error = get_input(2030, "1", &config(&mock.src_dir("locked")), None).unwrap_err();
+    assert!(matches!(error, AocError::NotUnlocked), "{:?}", error);
+}
+
+#[test]
+fn submits_answers_and_remembers_the_verdicts() {
+    let mock = Mock::get();
+    let src_dir = mock.src_dir("submit");
+
+    let submit = |answer| submit_answer(2019, "1", 1, answer, &config(&src_dir), None).unwrap();
+    assert_eq!(submit("100"), Verdict::TooHigh);
+    assert_eq!(submit("1"), Verdict::TooLow);
+    assert_eq!(submit("42"), Verdict::Correct);
+    assert_eq!(mock.requests("/2019/day/1/answer").len(), 3);
+
+    // Known answers and answers outside of the known bounds are not submitted again.
+    assert_eq!(submit("42"), Verdict::Correct);
+    assert_eq!(submit("200"), Verdict::Wrong);
+    assert_eq!(mock.requests("/2019/day/1/answer").len(), 3);
+
+    let answers =
+        std::fs::read_to_string(format!("{}/input/2019/day1.answers.txt", src_dir)).unwrap();
+    assert_eq!(answers, "1 too-high 100\n1 too-low 1\n1 correct 42\n");
+}
+
+#[test]
+fn remembers_multi_line_answers() {
+    let mock = Mock::get();
+    let src_dir = mock.src_dir("multi-line");
+
+    let answer = "42\n#.#\\n";
+    let verdict = submit_answer(2013, "1", 1, answer, &config(&src_dir), None).unwrap();
+    assert_eq!(verdict, Verdict::Correct);
+    let answers =
+        std::fs::read_to_string(format!("{}/input/2013/day1.answers.txt", src_dir)).unwrap();
+    assert_eq!(answers, "1 correct 42\\n#.#\\\\n\n");
+
+    assert_eq!(
+        answer_status(2013, "1", 1, answer, &config(&src_dir), None),
+        Status::Correct
+    );
+    assert_eq!(
+        answer_status(2013, "1", 1, "42", &config(&src_dir), None),
+        Status::Wrong
+    );
+}
+
+#[test]
+fn reports_the_rate_limit() {
+    let mock = Mock::get();
+    let src_dir = mock.src_dir("rate-limit");
+
+    let verdict = submit_answer(2020, "1", 2, "7", &config(&src_dir), None).unwrap();
+    assert_eq!(
+        verdict,
+        Verdict::RateLimited(std::time::Duration::from_secs(65))
+    );
+    assert!(!std::path::Path::new(&format!("{}/input/2020/day1.answers.txt", src_dir)).exists());
+}